@@ -0,0 +1,90 @@
+use crate::audit::policy::{AuditPolicy, PolicyViolation};
+use crate::audit::report::ZipAuditReport;
+use crate::audit::scan;
+use crate::inflate::extract_options::ExtractOptions;
+use crate::inflate::extraction_report::{ExtractionReport, error_message};
+use crate::inflate::zip_archive_extensions::ZipArchiveExtensions;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{Seek, SeekFrom};
+use std::path::Path;
+use zip::ZipArchive;
+use zip::result::{ZipError, ZipResult};
+
+/// The outcome of `zip_extract_audited`: the audit report, and the result of the extraction that
+/// was attempted only if the report satisfied the policy.
+#[derive(Debug)]
+pub struct AuditedExtraction {
+    pub audit: ZipAuditReport,
+    pub extraction: Result<ExtractionReport, AuditedExtractionError>,
+}
+
+#[derive(Debug)]
+pub enum AuditedExtractionError {
+    /// The audit report violates the policy; nothing was extracted.
+    PolicyViolated(Vec<PolicyViolation>),
+
+    /// The audit report satisfies the policy, but the extraction failed.
+    Extraction(ZipError),
+}
+
+impl fmt::Display for AuditedExtractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PolicyViolated(violations) => {
+                write!(f, "The archive violates the audit policy: {:?}", violations)
+            }
+            Self::Extraction(error) => {
+                write!(f, "The extraction failed: {}", error_message(error))
+            }
+        }
+    }
+}
+
+impl Error for AuditedExtractionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::PolicyViolated(_) => None,
+            Self::Extraction(error) => Some(error),
+        }
+    }
+}
+
+/// Audits a ZIP file and extracts it to the given directory, if the audit report satisfies the
+/// policy. An error is returned only if the archive cannot be audited at all.
+pub fn zip_extract_audited(
+    archive_file: &Path,
+    target_dir: &Path,
+    policy: &AuditPolicy,
+) -> ZipResult<AuditedExtraction> {
+    zip_extract_audited_with_options(archive_file, target_dir, policy, &ExtractOptions::default())
+}
+
+/// Audits a ZIP file and extracts it to the given directory using the specified options, if the
+/// audit report satisfies the policy.
+pub fn zip_extract_audited_with_options(
+    archive_file: &Path,
+    target_dir: &Path,
+    policy: &AuditPolicy,
+    options: &ExtractOptions,
+) -> ZipResult<AuditedExtraction> {
+    let mut file = File::open(archive_file)?;
+    let audit = scan::scan_zip(&mut file)?;
+
+    let violations = policy.violations(&audit);
+    if !violations.is_empty() {
+        return Ok(AuditedExtraction {
+            audit,
+            extraction: Err(AuditedExtractionError::PolicyViolated(violations)),
+        });
+    }
+
+    let extraction = file
+        .seek(SeekFrom::Start(0))
+        .map_err(ZipError::from)
+        .and_then(|_| ZipArchive::new(file))
+        .and_then(|mut archive| archive.extract_with_options(target_dir, options))
+        .map_err(AuditedExtractionError::Extraction);
+    Ok(AuditedExtraction { audit, extraction })
+}
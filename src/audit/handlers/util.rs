@@ -17,7 +17,8 @@ pub fn depth_hint_bytes(name: &[u8]) -> usize {
 
 pub fn compression_ratio(c: u64, u: u64) -> f64 {
     if c == 0 {
-        return f64::INFINITY;
+        // Empty entries do not expand; only data that inflates from nothing is suspicious.
+        return if u == 0 { 1.0 } else { f64::INFINITY };
     }
     (u as f64) / (c as f64)
 }
@@ -1,7 +1,9 @@
 pub use crate::audit::handlers::*;
 pub use crate::audit::utils::*;
 
+pub mod audited_extraction;
 pub mod handlers;
+pub mod policy;
 pub mod report;
 pub mod scan;
 pub mod utils;
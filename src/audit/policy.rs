@@ -0,0 +1,96 @@
+use crate::audit::report::{MAX_SUSPICIOUS_RATIO, ZipAuditReport};
+use std::path::PathBuf;
+
+/// Describes which findings of a `ZipAuditReport` are acceptable before an archive is extracted.
+/// The default policy is strict: it refuses every finding it knows about, and limits the
+/// compression ratio to `MAX_SUSPICIOUS_RATIO`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "audit-json", derive(serde::Serialize))]
+pub struct AuditPolicy {
+    pub allow_absolute_paths: bool,
+    pub allow_parent_components: bool,
+    pub allow_duplicate_names: bool,
+    pub allow_encrypted_entries: bool,
+    pub allow_symlinks_outside_root: bool,
+    pub max_ratio: Option<f64>,
+}
+
+/// A finding of a `ZipAuditReport` that is not permitted by an `AuditPolicy`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "audit-json", derive(serde::Serialize))]
+pub enum PolicyViolation {
+    /// The archive contains entries with absolute paths.
+    AbsolutePaths,
+
+    /// The archive contains entries whose names contain `..` components.
+    ParentComponents,
+
+    /// The archive contains entries that share the same name.
+    DuplicateNames(Vec<PathBuf>),
+
+    /// The archive contains encrypted entries.
+    EncryptedEntries(Vec<PathBuf>),
+
+    /// The archive contains symlinks that point outside the extraction root.
+    SymlinksOutsideRoot(usize),
+
+    /// The maximum compression ratio of the archive exceeds the permitted ratio.
+    ///
+    /// `max_ratio`: the highest ratio found in the archive
+    /// `limit`: the ratio permitted by the policy
+    CompressionRatio { max_ratio: f64, limit: f64 },
+}
+
+impl AuditPolicy {
+    pub fn new() -> Self {
+        Self {
+            allow_absolute_paths: false,
+            allow_parent_components: false,
+            allow_duplicate_names: false,
+            allow_encrypted_entries: false,
+            allow_symlinks_outside_root: false,
+            max_ratio: Some(MAX_SUSPICIOUS_RATIO),
+        }
+    }
+
+    /// Evaluates the report and returns all findings that violate this policy.
+    pub fn violations(&self, report: &ZipAuditReport) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+        if report.has_absolute_paths && !self.allow_absolute_paths {
+            violations.push(PolicyViolation::AbsolutePaths);
+        }
+        if report.has_parent_components && !self.allow_parent_components {
+            violations.push(PolicyViolation::ParentComponents);
+        }
+        if !report.duplicate_names.is_empty() && !self.allow_duplicate_names {
+            violations.push(PolicyViolation::DuplicateNames(
+                report.duplicate_names.clone(),
+            ));
+        }
+        if report.has_encrypted_entries && !self.allow_encrypted_entries {
+            violations.push(PolicyViolation::EncryptedEntries(
+                report.encrypted_entries.clone(),
+            ));
+        }
+        if report.symlinks_point_outside_root > 0 && !self.allow_symlinks_outside_root {
+            violations.push(PolicyViolation::SymlinksOutsideRoot(
+                report.symlinks_point_outside_root,
+            ));
+        }
+        if let Some(limit) = self.max_ratio
+            && report.max_ratio > limit
+        {
+            violations.push(PolicyViolation::CompressionRatio {
+                max_ratio: report.max_ratio,
+                limit,
+            });
+        }
+        violations
+    }
+}
+
+impl Default for AuditPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,9 +1,11 @@
 use crate::audit::handlers::entry_audit_handler::EntryAuditHandler;
-use crate::audit::report::ZipAuditReport;
+use crate::audit::report::{SuspiciousEntry, SuspiciousReason, ZipAuditReport};
 use crate::audit::scan;
+use crate::inflate::integrity::{EntryTestStatus, test_entries};
 use std::fs::File;
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
+use zip::ZipArchive;
 use zip::result::ZipResult;
 
 /// Produce a fast, side-effect-free risk assessment of a ZIP archive from a file path.
@@ -17,6 +19,42 @@ pub fn zip_audit<R: Read + Seek>(reader: R) -> ZipResult<ZipAuditReport> {
     scan::scan_zip(reader)
 }
 
+/// Produce a risk assessment of a ZIP archive from a file path, and verify the data of every
+/// entry (see `zip_audit_verified`).
+pub fn zip_audit_file_verified<P: AsRef<Path>>(archive: P) -> ZipResult<ZipAuditReport> {
+    let file = File::open(archive)?;
+    zip_audit_verified(file)
+}
+
+/// Reader-based audit API that also decompresses every entry to a sink and verifies its size and
+/// CRC32, like `zip_test`. Entries that fail the check are reported as
+/// `SuspiciousReason::HeaderMismatch`, and set `truncated_or_mismatch`. Does not extract or write
+/// anything.
+pub fn zip_audit_verified<R: Read + Seek>(mut reader: R) -> ZipResult<ZipAuditReport> {
+    let mut report = scan::scan_zip(&mut reader)?;
+    reader.seek(SeekFrom::Start(0))?;
+    let mut archive = ZipArchive::new(reader)?;
+    for result in test_entries(&mut archive)? {
+        if matches!(
+            result.status,
+            EntryTestStatus::Ok | EntryTestStatus::Encrypted
+        ) {
+            continue;
+        }
+        report.truncated_or_mismatch = true;
+        report.suspicious_entries.push(SuspiciousEntry {
+            name: result.name,
+            reason: SuspiciousReason::HeaderMismatch,
+        });
+    }
+    if report.truncated_or_mismatch {
+        report
+            .recommendations
+            .push("Reject archives whose entries fail the integrity test.".to_string());
+    }
+    Ok(report)
+}
+
 /// Reader-based audit API with a custom pipeline of entry-analysis handlers.
 ///
 /// This enables advanced users to inject their own stateful analysis handlers or
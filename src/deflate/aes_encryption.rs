@@ -0,0 +1,52 @@
+use crate::aes_encryption_handler::AesEncryptionHandler;
+use crate::default_entry_handler::DefaultEntryHandler;
+use crate::deflate::zip_writer_extensions::ZipWriterExtensions;
+use crate::entry_handler::EntryHandler;
+use std::fs::File;
+use std::path::PathBuf;
+use zip::result::ZipResult;
+use zip::write::SimpleFileOptions;
+use zip::{AesMode, ZipWriter};
+
+/// Creates a ZIP archive that contains the files and directories from the specified directory,
+/// and encrypts every file entry with AES, using the given password and key size.
+///
+/// Entry names and directory entries are not encrypted; only file contents are.
+pub fn zip_create_from_directory_encrypted(
+    archive_file: &PathBuf,
+    directory: &PathBuf,
+    password: &str,
+    mode: AesMode,
+) -> ZipResult<()> {
+    zip_create_from_directory_encrypted_with_handler(
+        archive_file,
+        directory,
+        password,
+        mode,
+        &DefaultEntryHandler,
+    )
+}
+
+/// Creates a ZIP archive like `zip_create_from_directory_encrypted`, but passes the entries to the
+/// given handler, such as a `ZipIgnoreEntryHandler`. Every file entry the handler adds is
+/// encrypted.
+pub fn zip_create_from_directory_encrypted_with_handler<H>(
+    archive_file: &PathBuf,
+    directory: &PathBuf,
+    password: &str,
+    mode: AesMode,
+    handler: &H,
+) -> ZipResult<()>
+where
+    H: EntryHandler<()>,
+{
+    let file = File::create(archive_file)?;
+    let mut zip_writer = ZipWriter::new(file);
+    zip_writer.create_from_directory_with_options(
+        directory,
+        |_| SimpleFileOptions::default(),
+        &AesEncryptionHandler::with_inner(handler, password, mode),
+    )?;
+    zip_writer.finish()?;
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::default_entry_handler::DefaultEntryHandler;
+use crate::entry_handler::{EntryHandler, EntryOutcome};
+use std::io;
+use std::io::Write;
+use std::path::PathBuf;
+use zip::result::ZipResult;
+use zip::write::{FileOptionExtension, FileOptions};
+use zip::{AesMode, ZipWriter};
+
+/// An `EntryHandler` wrapper that encrypts file entries with AES.
+///
+/// If the current entry is a file, the file options are extended with the configured password and
+/// AES mode before the entry is passed to the wrapped `inner` handler; directory entries are passed
+/// on unchanged. The entry is only encrypted if the inner handler writes it with the file options
+/// it is given, as `DefaultEntryHandler` does (see `EntryHandler` for how handlers are composed).
+pub struct AesEncryptionHandler<H = DefaultEntryHandler> {
+    password: String,
+    mode: AesMode,
+    inner: H,
+}
+
+impl AesEncryptionHandler<DefaultEntryHandler> {
+    pub fn new(password: &str, mode: AesMode) -> Self {
+        Self::with_inner(DefaultEntryHandler, password, mode)
+    }
+}
+
+impl<H> AesEncryptionHandler<H> {
+    pub fn with_inner(inner: H, password: &str, mode: AesMode) -> Self {
+        Self {
+            password: password.to_string(),
+            mode,
+            inner,
+        }
+    }
+}
+
+impl<T: FileOptionExtension, H> EntryHandler<T> for AesEncryptionHandler<H>
+where
+    H: EntryHandler<T>,
+{
+    fn handle_entry<W: Write + io::Seek>(
+        &self,
+        writer: &mut ZipWriter<W>,
+        root: &PathBuf,
+        entry_path: &PathBuf,
+        file_options: FileOptions<T>,
+    ) -> ZipResult<EntryOutcome> {
+        if std::fs::metadata(entry_path)?.is_file() {
+            let file_options = file_options.with_aes_encryption(self.mode, &self.password);
+            return self
+                .inner
+                .handle_entry(writer, root, entry_path, file_options);
+        }
+        self.inner
+            .handle_entry(writer, root, entry_path, file_options)
+    }
+}
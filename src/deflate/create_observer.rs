@@ -0,0 +1,135 @@
+use crate::entry_handler::{EntryHandler, EntryOutcome};
+use std::io;
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use zip::result::{ZipError, ZipResult};
+use zip::write::{FileOptionExtension, FileOptions};
+use zip::{ZipArchive, ZipWriter};
+
+/// Receives events while `ZipWriterExtensions::create_from_directory_with_options` walks a
+/// directory tree. All methods have empty default implementations, so observers only implement
+/// what they need.
+///
+/// Observers are attached with an `ObservingEntryHandler`; state must be kept behind interior
+/// mutability.
+pub trait CreateObserver: Send + Sync {
+    /// Called before a directory entry is passed to the wrapped handler.
+    fn entry_visited(&self, _entry_path: &Path) {}
+
+    /// Called after a directory entry has been written to the archive.
+    fn entry_added(&self, _entry_path: &Path, _bytes_read: u64) {}
+
+    /// Called if the wrapped handler did not write a directory entry to the archive. The entries
+    /// of pruned directories are not visited, and therefore not reported.
+    fn entry_ignored(&self, _entry_path: &Path) {}
+
+    /// Called if the wrapped handler failed; the error is returned to the caller afterward.
+    fn entry_failed(&self, _entry_path: &Path, _error: &ZipError) {}
+
+    /// Called for each entry of the finished archive, if it was created by
+    /// `zip_create_from_directory_observed`.
+    fn entry_compressed(&self, _name: &str, _uncompressed_size: u64, _compressed_size: u64) {}
+}
+
+impl<O: CreateObserver + ?Sized> CreateObserver for &O {
+    fn entry_visited(&self, entry_path: &Path) {
+        (**self).entry_visited(entry_path)
+    }
+
+    fn entry_added(&self, entry_path: &Path, bytes_read: u64) {
+        (**self).entry_added(entry_path, bytes_read)
+    }
+
+    fn entry_ignored(&self, entry_path: &Path) {
+        (**self).entry_ignored(entry_path)
+    }
+
+    fn entry_failed(&self, entry_path: &Path, error: &ZipError) {
+        (**self).entry_failed(entry_path, error)
+    }
+
+    fn entry_compressed(&self, name: &str, uncompressed_size: u64, compressed_size: u64) {
+        (**self).entry_compressed(name, uncompressed_size, compressed_size)
+    }
+}
+
+impl<O: CreateObserver + ?Sized> CreateObserver for Arc<O> {
+    fn entry_visited(&self, entry_path: &Path) {
+        (**self).entry_visited(entry_path)
+    }
+
+    fn entry_added(&self, entry_path: &Path, bytes_read: u64) {
+        (**self).entry_added(entry_path, bytes_read)
+    }
+
+    fn entry_ignored(&self, entry_path: &Path) {
+        (**self).entry_ignored(entry_path)
+    }
+
+    fn entry_failed(&self, entry_path: &Path, error: &ZipError) {
+        (**self).entry_failed(entry_path, error)
+    }
+
+    fn entry_compressed(&self, name: &str, uncompressed_size: u64, compressed_size: u64) {
+        (**self).entry_compressed(name, uncompressed_size, compressed_size)
+    }
+}
+
+/// An `EntryHandler` wrapper that reports every visited entry, and what the wrapped `inner`
+/// handler did with it, to a `CreateObserver`.
+///
+/// Wrap the outermost handler to observe all visited entries, for instance
+/// `ObservingEntryHandler::new(ZipIgnoreEntryHandler::new(), observer)`; entries excluded by
+/// `.zipignore` rules are then reported as ignored.
+pub struct ObservingEntryHandler<H, O> {
+    inner: H,
+    observer: O,
+}
+
+impl<H, O> ObservingEntryHandler<H, O> {
+    pub fn new(inner: H, observer: O) -> Self {
+        Self { inner, observer }
+    }
+}
+
+impl<T: FileOptionExtension, H, O> EntryHandler<T> for ObservingEntryHandler<H, O>
+where
+    H: EntryHandler<T>,
+    O: CreateObserver,
+{
+    fn handle_entry<W: Write + io::Seek>(
+        &self,
+        writer: &mut ZipWriter<W>,
+        root: &PathBuf,
+        entry_path: &PathBuf,
+        file_options: FileOptions<T>,
+    ) -> ZipResult<EntryOutcome> {
+        self.observer.entry_visited(entry_path);
+        let result = self
+            .inner
+            .handle_entry(writer, root, entry_path, file_options);
+        match &result {
+            Ok(EntryOutcome::Added { bytes_read }) => {
+                self.observer.entry_added(entry_path, *bytes_read)
+            }
+            Ok(EntryOutcome::Ignored | EntryOutcome::Pruned) => {
+                self.observer.entry_ignored(entry_path)
+            }
+            Err(error) => self.observer.entry_failed(entry_path, error),
+        }
+        result
+    }
+}
+
+/// Reports the uncompressed and compressed size of each entry of a finished archive.
+pub(crate) fn notify_compressed_sizes<R: Read + Seek, O: CreateObserver>(
+    archive: &mut ZipArchive<R>,
+    observer: &O,
+) -> ZipResult<()> {
+    for file_number in 0..archive.len() {
+        let entry = archive.by_index_raw(file_number)?;
+        observer.entry_compressed(entry.name(), entry.size(), entry.compressed_size());
+    }
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::entry_handler::EntryHandler;
+use crate::entry_handler::{EntryHandler, EntryOutcome};
 use crate::file_utils::{make_relative_path, path_as_string};
 use std::fs::File;
 use std::io;
@@ -17,20 +17,22 @@ impl<T: FileOptionExtension> EntryHandler<T> for DefaultEntryHandler {
         root: &PathBuf,
         entry_path: &PathBuf,
         file_options: FileOptions<T>,
-        buffer: &mut Vec<u8>,
-    ) -> ZipResult<()> {
+    ) -> ZipResult<EntryOutcome> {
         let metadata = std::fs::metadata(entry_path)?;
         let relative = make_relative_path(root, entry_path);
 
         if metadata.is_file() {
-            let mut f = File::open(&entry_path)?;
-            f.read_to_end(buffer)?;
+            // Copy no more than the size observed when the entry was visited; files that grow
+            // while being archived (such as the archive itself, if it is written into the source
+            // directory) would otherwise be read indefinitely.
+            let f = File::open(&entry_path)?;
             writer.start_file(path_as_string(&relative), file_options)?;
-            writer.write_all(buffer.as_ref())?;
-            buffer.clear();
+            let bytes_read = io::copy(&mut f.take(metadata.len()), writer)?;
+            return Ok(EntryOutcome::Added { bytes_read });
         } else if metadata.is_dir() {
             writer.add_directory(path_as_string(&relative), file_options)?;
+            return Ok(EntryOutcome::Added { bytes_read: 0 });
         }
-        Ok(())
+        Ok(EntryOutcome::Ignored)
     }
 }
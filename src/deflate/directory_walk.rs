@@ -0,0 +1,233 @@
+use crate::walk_options::{SymlinkPolicy, WalkOptions};
+use std::fs::{Metadata, ReadDir};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Iterates over the entries below a directory in the order in which
+/// `ZipWriterExtensions::create_from_directory_with_options` adds them to an archive: the entries
+/// of a directory are visited before the entries of its subdirectories, and subdirectories are
+/// visited last-found-first. Whether symbolic links to directories are followed, how deep the walk
+/// descends, and whether it leaves the file system of the directory is decided by the
+/// `WalkOptions`.
+pub(crate) struct DirectoryWalk {
+    paths_queue: Vec<QueuedDirectory>,
+    current: Option<(Listing, QueuedDirectory)>,
+    sorted: bool,
+    options: WalkOptions,
+    root_device: Option<u64>,
+}
+
+/// A directory whose entries are visited later.
+struct QueuedDirectory {
+    path: PathBuf,
+    depth: usize,
+    /// The identities of the directory and its ancestors; only tracked if cycles are detected.
+    ancestors: Vec<FileId>,
+}
+
+/// The remaining entries of the directory that is currently visited.
+enum Listing {
+    Unsorted(ReadDir),
+    Sorted(std::vec::IntoIter<io::Result<PathBuf>>),
+}
+
+impl DirectoryWalk {
+    pub(crate) fn new(directory: &Path, options: &WalkOptions) -> Self {
+        let metadata = std::fs::metadata(directory).ok();
+        let mut ancestors = Vec::new();
+        if options.symlinks == SymlinkPolicy::FollowWithCycleDetection
+            && let Some(metadata) = &metadata
+            && let Ok(id) = file_id(directory, metadata)
+        {
+            ancestors.push(id);
+        }
+        let root = QueuedDirectory {
+            path: directory.to_path_buf(),
+            depth: 0,
+            ancestors,
+        };
+        Self {
+            paths_queue: if options.max_depth == Some(0) {
+                Vec::new()
+            } else {
+                vec![root]
+            },
+            current: None,
+            sorted: false,
+            options: options.clone(),
+            root_device: metadata.as_ref().and_then(device),
+        }
+    }
+
+    /// Creates a walk whose order does not depend on the file system: the entries of each
+    /// directory are visited in the byte order of their names, and subdirectories are visited in
+    /// the same order, after the entries of their parent.
+    pub(crate) fn sorted(directory: &Path, options: &WalkOptions) -> Self {
+        Self {
+            sorted: true,
+            ..Self::new(directory, options)
+        }
+    }
+
+    /// Returns `true` if the entries of the directory at `path`, which was returned by the walk,
+    /// are going to be visited.
+    pub(crate) fn is_queued(&self, path: &Path) -> bool {
+        self.queued_position(path).is_some()
+    }
+
+    /// Skips the entries of the directory at `path`, which was returned by the walk, such as if the
+    /// entry handler returned `EntryOutcome::Pruned`. Has no effect for other entries.
+    ///
+    /// A directory is only listed after all entries of its parent have been returned, so pruning
+    /// the directory right after it was returned ensures that its entries are never enumerated.
+    pub(crate) fn prune(&mut self, path: &Path) {
+        if let Some(position) = self.queued_position(path) {
+            self.paths_queue.remove(position);
+        }
+    }
+
+    fn queued_position(&self, path: &Path) -> Option<usize> {
+        // Directories are queued last, so recently returned directories are found near the end.
+        self.paths_queue
+            .iter()
+            .rposition(|directory| directory.path == path)
+    }
+
+    fn read_listing(&mut self, directory: &QueuedDirectory) -> io::Result<Listing> {
+        let entries = std::fs::read_dir(&directory.path)?;
+        if !self.sorted {
+            return Ok(Listing::Unsorted(entries));
+        }
+        let mut paths = entries
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<PathBuf>>>()?;
+        paths.sort();
+        let mut subdirectories = Vec::new();
+        let mut listing = Vec::with_capacity(paths.len());
+        for path in paths {
+            match self.descend(directory, &path) {
+                Ok(subdirectory) => {
+                    subdirectories.extend(subdirectory);
+                    listing.push(Ok(path));
+                }
+                Err(error) => listing.push(Err(error)),
+            }
+        }
+        // The queue is a stack, so subdirectories are pushed in reverse to be visited in order.
+        self.paths_queue.extend(subdirectories.into_iter().rev());
+        Ok(Listing::Sorted(listing.into_iter()))
+    }
+
+    /// Decides whether the entries of `entry_path`, an entry of `parent`, are visited. Returns the
+    /// directory to queue, or `None` if the entry is not a directory, or must not be descended
+    /// into.
+    fn descend(
+        &self,
+        parent: &QueuedDirectory,
+        entry_path: &Path,
+    ) -> io::Result<Option<QueuedDirectory>> {
+        let depth = parent.depth + 1;
+        if self
+            .options
+            .max_depth
+            .is_some_and(|max_depth| depth >= max_depth)
+        {
+            return Ok(None);
+        }
+        let is_symlink = std::fs::symlink_metadata(entry_path)?.is_symlink();
+        if is_symlink && self.options.symlinks == SymlinkPolicy::DontFollow {
+            return Ok(None);
+        }
+        let entry_metadata = std::fs::metadata(entry_path)?;
+        if !entry_metadata.is_dir() {
+            return Ok(None);
+        }
+        if self.options.same_file_system && device(&entry_metadata) != self.root_device {
+            return Ok(None);
+        }
+        let mut ancestors = Vec::new();
+        if self.options.symlinks == SymlinkPolicy::FollowWithCycleDetection {
+            let id = file_id(entry_path, &entry_metadata)?;
+            if parent.ancestors.contains(&id) {
+                return Ok(None);
+            }
+            ancestors.reserve(parent.ancestors.len() + 1);
+            ancestors.extend(parent.ancestors.iter().cloned());
+            ancestors.push(id);
+        }
+        Ok(Some(QueuedDirectory {
+            path: entry_path.to_path_buf(),
+            depth,
+            ancestors,
+        }))
+    }
+}
+
+impl Iterator for DirectoryWalk {
+    type Item = io::Result<PathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.current.is_none() {
+                let next = self.paths_queue.pop()?;
+                match self.read_listing(&next) {
+                    Ok(listing) => self.current = Some((listing, next)),
+                    Err(error) => return Some(Err(error)),
+                }
+            }
+            let entry = match &mut self.current.as_mut()?.0 {
+                Listing::Sorted(paths) => match paths.next() {
+                    Some(path) => return Some(path),
+                    None => None,
+                },
+                Listing::Unsorted(entries) => entries.next(),
+            };
+            let entry_path = match entry {
+                Some(Ok(entry)) => entry.path(),
+                Some(Err(error)) => return Some(Err(error)),
+                None => {
+                    self.current = None;
+                    continue;
+                }
+            };
+            let directory = &self.current.as_ref()?.1;
+            return match self.descend(directory, &entry_path) {
+                Ok(subdirectory) => {
+                    self.paths_queue.extend(subdirectory);
+                    Some(Ok(entry_path))
+                }
+                Err(error) => Some(Err(error)),
+            };
+        }
+    }
+}
+
+#[cfg(unix)]
+type FileId = (u64, u64);
+
+#[cfg(not(unix))]
+type FileId = PathBuf;
+
+/// Identifies a directory by its device and inode number.
+#[cfg(unix)]
+fn file_id(_path: &Path, metadata: &Metadata) -> io::Result<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    Ok((metadata.dev(), metadata.ino()))
+}
+
+/// Identifies a directory by its canonical path, since inode numbers are not available.
+#[cfg(not(unix))]
+fn file_id(path: &Path, _metadata: &Metadata) -> io::Result<FileId> {
+    std::fs::canonicalize(path)
+}
+
+#[cfg(unix)]
+fn device(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.dev())
+}
+
+#[cfg(not(unix))]
+fn device(_metadata: &Metadata) -> Option<u64> {
+    None
+}
@@ -5,6 +5,31 @@ use zip::ZipWriter;
 use zip::result::ZipResult;
 use zip::write::{FileOptionExtension, FileOptions};
 
+/// Describes what an `EntryHandler` did with a visited directory entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryOutcome {
+    /// The entry was written to the archive.
+    ///
+    /// `bytes_read`: the number of bytes read from the source file; `0` for directories and links
+    Added { bytes_read: u64 },
+
+    /// The entry was not written to the archive.
+    Ignored,
+
+    /// The entry was not written to the archive, and, if it is a directory, none of its entries are
+    /// visited. Returning this for ignored directories, such as `node_modules` or `target`, spares
+    /// enumerating their contents.
+    Pruned,
+}
+
+/// Writes a single directory entry visited by `ZipWriterExtensions::create_from_directory_with_options`
+/// to the archive. Implementations are expected to stream file contents into the writer, rather
+/// than buffering whole files in memory.
+///
+/// Handlers are composed by wrapping: a wrapper handler, such as `ZipIgnoreEntryHandler`, holds an
+/// `inner` handler, which is `DefaultEntryHandler` unless another handler is passed to its
+/// `with_inner` constructor, and decides whether, and with which file options, each entry is
+/// passed on to it.
 pub trait EntryHandler<T: FileOptionExtension> {
     fn handle_entry<W: Write + io::Seek>(
         &self,
@@ -12,6 +37,17 @@ pub trait EntryHandler<T: FileOptionExtension> {
         root: &PathBuf,
         entry_path: &PathBuf,
         file_options: FileOptions<T>,
-        buffer: &mut Vec<u8>,
-    ) -> ZipResult<()>;
+    ) -> ZipResult<EntryOutcome>;
+}
+
+impl<T: FileOptionExtension, H: EntryHandler<T>> EntryHandler<T> for &H {
+    fn handle_entry<W: Write + io::Seek>(
+        &self,
+        writer: &mut ZipWriter<W>,
+        root: &PathBuf,
+        entry_path: &PathBuf,
+        file_options: FileOptions<T>,
+    ) -> ZipResult<EntryOutcome> {
+        (**self).handle_entry(writer, root, entry_path, file_options)
+    }
 }
@@ -1,7 +1,18 @@
+#[cfg(feature = "aes-crypto")]
+pub mod aes_encryption;
+#[cfg(feature = "aes-crypto")]
+pub mod aes_encryption_handler;
+pub mod create_observer;
 pub mod default_entry_handler;
+mod directory_walk;
 pub mod entry_handler;
+mod parallel_compression;
+pub mod preserve_metadata_handler;
 pub mod preserve_symlinks;
 pub mod preserve_symlinks_handler;
+pub mod reproducible;
+pub mod reproducible_entry_handler;
+pub mod walk_options;
 pub mod zip_ignore_entry_handler;
 pub mod zip_writer;
 pub mod zip_writer_extensions;
@@ -0,0 +1,287 @@
+use crate::deflate::directory_walk::DirectoryWalk;
+use crate::entry_handler::{EntryHandler, EntryOutcome};
+use crate::file_utils::create_temp_file;
+use crate::walk_options::WalkOptions;
+use std::fs::File;
+use std::io;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use zip::result::ZipResult;
+use zip::write::{FileOptionExtension, FileOptions};
+use zip::{ZipArchive, ZipWriter};
+
+/// Source files larger than this are compressed into a temporary file instead of memory.
+const SPILL_THRESHOLD: u64 = 8 * 1024 * 1024;
+
+/// The number of compressed entries per worker that may wait to be copied into the archive.
+const ENTRIES_IN_FLIGHT_PER_WORKER: usize = 2;
+
+/// Holds the compressed entries produced by a handler for a single source entry.
+enum EntryBuffer {
+    Memory(Cursor<Vec<u8>>),
+    Spill { file: File, path: PathBuf },
+}
+
+impl EntryBuffer {
+    fn for_entry(entry_path: &Path) -> io::Result<Self> {
+        let size = std::fs::metadata(entry_path).map_or(0, |m| m.len());
+        if size <= SPILL_THRESHOLD {
+            return Ok(Self::Memory(Cursor::new(Vec::new())));
+        }
+        let (file, path) = create_temp_file(&std::env::temp_dir(), "zip-extensions-")?;
+        Ok(Self::Spill { file, path })
+    }
+}
+
+impl Read for EntryBuffer {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Memory(cursor) => cursor.read(buf),
+            Self::Spill { file, .. } => file.read(buf),
+        }
+    }
+}
+
+impl Write for EntryBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Memory(cursor) => cursor.write(buf),
+            Self::Spill { file, .. } => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Memory(cursor) => cursor.flush(),
+            Self::Spill { file, .. } => file.flush(),
+        }
+    }
+}
+
+impl Seek for EntryBuffer {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Self::Memory(cursor) => cursor.seek(pos),
+            Self::Spill { file, .. } => file.seek(pos),
+        }
+    }
+}
+
+impl Drop for EntryBuffer {
+    fn drop(&mut self) {
+        if let Self::Spill { path, .. } = self {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+type CompressedEntry = thread::Result<ZipResult<ZipArchive<EntryBuffer>>>;
+
+/// A source entry handed out to a worker.
+struct Job {
+    index: usize,
+    entry_path: PathBuf,
+    /// Whether the walk is going to visit the entries of the source entry, which is a directory.
+    descends: bool,
+}
+
+struct PipelineState {
+    walk: DirectoryWalk,
+    walk_finished: bool,
+    /// The number of handed-out directory jobs that have not completed yet.
+    pending_directories: usize,
+    copied: usize,
+    cancelled: bool,
+    results: Vec<Option<CompressedEntry>>,
+}
+
+/// Walks the source directory while handing out source entries to the workers, and hands out
+/// compressed entries to the writing thread.
+///
+/// The walk only advances while no directory job is pending, so that a directory pruned by the
+/// handler is removed from the walk before its entries would be listed.
+struct Pipeline {
+    state: Mutex<PipelineState>,
+    changed: Condvar,
+    window: usize,
+}
+
+impl Pipeline {
+    /// Returns the next source entry to compress, or `None` if the walk is finished or the
+    /// pipeline was cancelled. Blocks while too many compressed entries wait to be copied, or while
+    /// a directory job is pending.
+    fn next_job(&self) -> Option<Job> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if state.cancelled || state.walk_finished {
+                return None;
+            }
+            let index = state.results.len();
+            if index < state.copied + self.window && state.pending_directories == 0 {
+                match state.walk.next() {
+                    Some(Ok(entry_path)) => {
+                        let descends = state.walk.is_queued(&entry_path);
+                        if descends {
+                            state.pending_directories += 1;
+                        }
+                        state.results.push(None);
+                        return Some(Job {
+                            index,
+                            entry_path,
+                            descends,
+                        });
+                    }
+                    Some(Err(error)) => {
+                        // The error is returned by the writing thread once it reaches the entry.
+                        state.results.push(Some(Ok(Err(error.into()))));
+                        state.walk_finished = true;
+                    }
+                    None => state.walk_finished = true,
+                }
+                self.changed.notify_all();
+                return None;
+            }
+            state = self.changed.wait(state).unwrap();
+        }
+    }
+
+    fn complete(&self, job: &Job, entry: CompressedEntry, pruned: bool) {
+        let mut state = self.state.lock().unwrap();
+        if pruned {
+            state.walk.prune(&job.entry_path);
+        }
+        if job.descends {
+            state.pending_directories -= 1;
+        }
+        state.results[job.index] = Some(entry);
+        self.changed.notify_all();
+    }
+
+    /// Waits for the compressed entry of the given source entry. Returns `None` if the walk
+    /// finished before reaching the entry.
+    fn take(&self, job: usize) -> Option<CompressedEntry> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(entry) = state.results.get_mut(job).and_then(Option::take) {
+                return Some(entry);
+            }
+            if state.walk_finished && job >= state.results.len() {
+                return None;
+            }
+            state = self.changed.wait(state).unwrap();
+        }
+    }
+
+    fn copied(&self) {
+        self.state.lock().unwrap().copied += 1;
+        self.changed.notify_all();
+    }
+
+    fn cancel(&self) {
+        self.state.lock().unwrap().cancelled = true;
+        self.changed.notify_all();
+    }
+}
+
+/// Adds the entries below `directory` to the writer, like
+/// `ZipWriterExtensions::create_from_directory_with_walk_options`, but runs the handler on up to
+/// `threads` workers. Each worker writes the entries of a source entry into a separate in-memory
+/// archive (or a temporary file, for large sources), whose compressed entries are then copied into
+/// the writer in walk order.
+pub(crate) fn create_from_directory_parallel<W, F, T, H>(
+    writer: &mut ZipWriter<W>,
+    directory: &Path,
+    cb_file_options: F,
+    handler: &H,
+    walk_options: &WalkOptions,
+    threads: usize,
+) -> ZipResult<()>
+where
+    W: Write + Seek,
+    T: FileOptionExtension,
+    F: Fn(&PathBuf) -> FileOptions<T> + Sync,
+    H: EntryHandler<T> + Sync,
+{
+    let threads = match NonZeroUsize::new(threads) {
+        Some(threads) => threads.get(),
+        None => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+    };
+
+    let pipeline = Pipeline {
+        state: Mutex::new(PipelineState {
+            walk: DirectoryWalk::new(directory, walk_options),
+            walk_finished: false,
+            pending_directories: 0,
+            copied: 0,
+            cancelled: false,
+            results: Vec::new(),
+        }),
+        changed: Condvar::new(),
+        window: threads * ENTRIES_IN_FLIGHT_PER_WORKER,
+    };
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                while let Some(job) = pipeline.next_job() {
+                    let entry = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        compress_entry(directory, &job.entry_path, &cb_file_options, handler)
+                    }));
+                    let pruned = matches!(entry, Ok(Ok((_, EntryOutcome::Pruned))));
+                    let entry = entry.map(|result| result.map(|(archive, _)| archive));
+                    pipeline.complete(&job, entry, pruned);
+                }
+            });
+        }
+
+        let mut job = 0;
+        while let Some(entry) = pipeline.take(job) {
+            let result = match entry {
+                Ok(entry) => entry.and_then(|mut archive| copy_entries(writer, &mut archive)),
+                Err(panic) => {
+                    pipeline.cancel();
+                    std::panic::resume_unwind(panic);
+                }
+            };
+            if let Err(error) = result {
+                pipeline.cancel();
+                return Err(error);
+            }
+            pipeline.copied();
+            job += 1;
+        }
+        Ok(())
+    })
+}
+
+fn compress_entry<F, T, H>(
+    root: &Path,
+    entry_path: &Path,
+    cb_file_options: &F,
+    handler: &H,
+) -> ZipResult<(ZipArchive<EntryBuffer>, EntryOutcome)>
+where
+    T: FileOptionExtension,
+    F: Fn(&PathBuf) -> FileOptions<T>,
+    H: EntryHandler<T>,
+{
+    let mut entry_writer = ZipWriter::new(EntryBuffer::for_entry(entry_path)?);
+    // The file options callback and `EntryHandler::handle_entry` take `&PathBuf`.
+    let (root, entry_path) = (root.to_path_buf(), entry_path.to_path_buf());
+    let file_options = cb_file_options(&entry_path);
+    let outcome = handler.handle_entry(&mut entry_writer, &root, &entry_path, file_options)?;
+    Ok((entry_writer.finish_into_readable()?, outcome))
+}
+
+fn copy_entries<W: Write + Seek>(
+    writer: &mut ZipWriter<W>,
+    archive: &mut ZipArchive<EntryBuffer>,
+) -> ZipResult<()> {
+    for file_number in 0..archive.len() {
+        writer.raw_copy_file(archive.by_index_raw(file_number)?)?;
+    }
+    Ok(())
+}
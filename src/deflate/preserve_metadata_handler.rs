@@ -0,0 +1,138 @@
+use crate::date_time_utils::unix_seconds_to_date_time;
+use crate::default_entry_handler::DefaultEntryHandler;
+use crate::entry_handler::{EntryHandler, EntryOutcome};
+use std::fs::Metadata;
+use std::io;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+use zip::ZipWriter;
+use zip::result::ZipResult;
+use zip::write::{ExtendedFileOptions, FileOptionExtension, FileOptions};
+
+/// The header ID of the extended timestamp extra field.
+const EXTENDED_TIMESTAMP_HEADER_ID: u16 = 0x5455;
+
+/// File option extensions that `PreserveMetadataHandler` can store a modification time in.
+///
+/// `FullFileOptions` receive an extended timestamp extra field, which holds the modification time
+/// with a precision of one second, in UTC, in addition to the MS-DOS timestamp. `SimpleFileOptions`
+/// cannot carry extra fields; their entries only store the MS-DOS timestamp.
+pub trait ExtendedTimestampSupport: FileOptionExtension {
+    /// Stores the given modification time, in seconds since the Unix epoch, in the file options.
+    fn store_modified_time(
+        options: FileOptions<'_, Self>,
+        modified: i64,
+    ) -> ZipResult<FileOptions<'_, Self>>;
+}
+
+impl ExtendedTimestampSupport for () {
+    fn store_modified_time(
+        options: FileOptions<'_, Self>,
+        modified: i64,
+    ) -> ZipResult<FileOptions<'_, Self>> {
+        Ok(options.last_modified_time(unix_seconds_to_date_time(modified)))
+    }
+}
+
+impl ExtendedTimestampSupport for ExtendedFileOptions {
+    fn store_modified_time(
+        options: FileOptions<'_, Self>,
+        modified: i64,
+    ) -> ZipResult<FileOptions<'_, Self>> {
+        let mut options = options.last_modified_time(unix_seconds_to_date_time(modified));
+        // The extended timestamp holds a signed 32-bit value, so times before 1970 can be stored
+        // as well; times outside of its range only keep the MS-DOS timestamp.
+        if let Ok(modified) = i32::try_from(modified) {
+            // Flags (bit 0: the modification time is present), followed by the modification time.
+            let mut data = vec![0x01];
+            data.extend_from_slice(&modified.to_le_bytes());
+            options.add_extra_data(EXTENDED_TIMESTAMP_HEADER_ID, data, false)?;
+        }
+        Ok(options)
+    }
+}
+
+/// An `EntryHandler` wrapper that copies the metadata of the source files into the archive.
+///
+/// The modification time of every entry is stored in `last_modified_time`, and in the extended
+/// timestamp extra field (see `ExtendedTimestampSupport`). The MS-DOS timestamp holds the UTC
+/// time, not the local time, so that the archive does not depend on the time zone of the host;
+/// tools that read it as local time show entries without an extended timestamp shifted by the UTC
+/// offset of the reader. Use `FullFileOptions` to store the extended timestamp as well. On Unix
+/// platforms, the permission bits of files and directories are stored as well. The entry is then
+/// passed to the wrapped `inner` handler (see `EntryHandler` for how handlers are composed);
+/// symbolic links keep the default permissions of the entry written by it, so wrapping a
+/// `PreserveSymlinksHandler` stores links without the permissions of their targets.
+pub struct PreserveMetadataHandler<H = DefaultEntryHandler> {
+    inner: H,
+}
+
+impl PreserveMetadataHandler<DefaultEntryHandler> {
+    pub fn new() -> Self {
+        Self::with_inner(DefaultEntryHandler)
+    }
+}
+
+impl Default for PreserveMetadataHandler<DefaultEntryHandler> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H> PreserveMetadataHandler<H> {
+    pub fn with_inner(inner: H) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: ExtendedTimestampSupport, H> EntryHandler<T> for PreserveMetadataHandler<H>
+where
+    H: EntryHandler<T>,
+{
+    fn handle_entry<W: Write + io::Seek>(
+        &self,
+        writer: &mut ZipWriter<W>,
+        root: &PathBuf,
+        entry_path: &PathBuf,
+        file_options: FileOptions<T>,
+    ) -> ZipResult<EntryOutcome> {
+        let symlink_metadata = std::fs::symlink_metadata(entry_path)?;
+        // Links are followed, unless they are dangling, since the inner handler may either write
+        // the link target or the link itself.
+        let metadata = std::fs::metadata(entry_path).unwrap_or(symlink_metadata.clone());
+
+        let mut file_options = file_options;
+        if let Some(modified) = modified_seconds(&metadata) {
+            file_options = T::store_modified_time(file_options, modified)?;
+        }
+        if !symlink_metadata.is_symlink()
+            && let Some(mode) = unix_mode(&metadata)
+        {
+            file_options = file_options.unix_permissions(mode);
+        }
+        self.inner
+            .handle_entry(writer, root, entry_path, file_options)
+    }
+}
+
+/// Returns the modification time in seconds since the Unix epoch, or `None` if the platform does
+/// not provide it.
+fn modified_seconds(metadata: &Metadata) -> Option<i64> {
+    let modified = metadata.modified().ok()?;
+    match modified.duration_since(UNIX_EPOCH) {
+        Ok(duration) => i64::try_from(duration.as_secs()).ok(),
+        Err(error) => i64::try_from(error.duration().as_secs()).ok().map(|s| -s),
+    }
+}
+
+#[cfg(unix)]
+fn unix_mode(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode())
+}
+
+#[cfg(not(unix))]
+fn unix_mode(_metadata: &Metadata) -> Option<u32> {
+    None
+}
@@ -24,7 +24,7 @@ use zip::write::{FileOptionExtension, FileOptions};
 /// This library's own extraction implementation protects against such
 /// attacks by sanitizing all paths with `ZipFile::enclosed_name`, and
 /// only creating symlinks whose canonicalized target remains inside the
-/// extraction root.
+/// extraction root (see `ExtractOptions::restore_symlinks`).
 ///
 /// **However, these guarantees do not apply to other extractors.**
 /// Developers distributing archives to unknown consumers should prefer
@@ -1,5 +1,5 @@
 use crate::default_entry_handler::DefaultEntryHandler;
-use crate::entry_handler::EntryHandler;
+use crate::entry_handler::{EntryHandler, EntryOutcome};
 use crate::file_utils::make_relative_path;
 use std::io;
 use std::io::Write;
@@ -46,8 +46,7 @@ where
         root: &PathBuf,
         entry_path: &PathBuf,
         file_options: FileOptions<T>,
-        buffer: &mut Vec<u8>,
-    ) -> ZipResult<()> {
+    ) -> ZipResult<EntryOutcome> {
         let symlink_metadata = std::fs::symlink_metadata(entry_path)?;
         let relative = make_relative_path(root, entry_path);
 
@@ -58,10 +57,10 @@ where
                 target.to_str().unwrap(),
                 file_options,
             )?;
-            return Ok(());
+            return Ok(EntryOutcome::Added { bytes_read: 0 });
         }
 
         self.inner
-            .handle_entry(writer, root, entry_path, file_options, buffer)
+            .handle_entry(writer, root, entry_path, file_options)
     }
 }
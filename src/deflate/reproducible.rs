@@ -0,0 +1,125 @@
+use crate::date_time_utils::unix_seconds_to_date_time;
+use crate::default_entry_handler::DefaultEntryHandler;
+use crate::deflate::zip_writer_extensions::ZipWriterExtensions;
+use std::fs::File;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use zip::result::{ZipError, ZipResult};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, DateTime, ZipWriter};
+
+/// The environment variable that holds the timestamp of reproducible builds, in seconds since the
+/// Unix epoch (see <https://reproducible-builds.org/specs/source-date-epoch/>).
+pub const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+/// Options that control how `ZipWriterExtensions::create_from_directory_reproducible` normalizes
+/// the metadata of the entries it writes.
+///
+/// The default stamps every entry with 1980-01-01 00:00:00, the earliest MS-DOS timestamp, and
+/// writes files with `0o644`, executable files with `0o755`, and directories with `0o755`.
+#[derive(Debug, Clone, Copy)]
+pub struct ReproducibleOptions {
+    pub(crate) timestamp: DateTime,
+    pub(crate) file_permissions: u32,
+    pub(crate) executable_permissions: u32,
+    pub(crate) directory_permissions: u32,
+}
+
+impl ReproducibleOptions {
+    pub fn new() -> Self {
+        Self {
+            timestamp: DateTime::default(),
+            file_permissions: 0o644,
+            executable_permissions: 0o755,
+            directory_permissions: 0o755,
+        }
+    }
+
+    /// Creates options whose timestamp is read from the `SOURCE_DATE_EPOCH` environment variable,
+    /// if it is set; otherwise, the default timestamp is used. Fails with
+    /// `ErrorKind::InvalidInput` if the variable is not a non-negative integer.
+    pub fn from_source_date_epoch() -> ZipResult<Self> {
+        let value = std::env::var(SOURCE_DATE_EPOCH).ok();
+        Self::from_source_date_epoch_value(value.as_deref())
+    }
+
+    pub(crate) fn from_source_date_epoch_value(value: Option<&str>) -> ZipResult<Self> {
+        let options = Self::new();
+        match value {
+            None => Ok(options),
+            Some(value) => match value.trim().parse::<u64>() {
+                Ok(seconds) => Ok(options.source_date_epoch(seconds)),
+                Err(_) => Err(ZipError::Io(io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "The SOURCE_DATE_EPOCH environment variable is not a valid timestamp.",
+                ))),
+            },
+        }
+    }
+
+    /// Sets the modification time of all entries.
+    pub fn timestamp(mut self, timestamp: DateTime) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the modification time of all entries from seconds since the Unix epoch. The MS-DOS
+    /// timestamp holds the UTC time, as the `SOURCE_DATE_EPOCH` specification requires, so that
+    /// the archive does not depend on the time zone of the host; tools that read it as local time
+    /// show it shifted by their UTC offset. Times outside the range of MS-DOS timestamps (1980 to
+    /// 2107) are clamped.
+    pub fn source_date_epoch(self, seconds: u64) -> Self {
+        let seconds = i64::try_from(seconds).unwrap_or(i64::MAX);
+        self.timestamp(unix_seconds_to_date_time(seconds))
+    }
+
+    /// Sets the permission bits of file entries whose source file is not executable. The default
+    /// is `0o644`.
+    pub fn file_permissions(mut self, file_permissions: u32) -> Self {
+        self.file_permissions = file_permissions;
+        self
+    }
+
+    /// Sets the permission bits of file entries whose source file is executable by its owner. The
+    /// default is `0o755`. Source files are never executable on non-Unix platforms.
+    pub fn executable_permissions(mut self, executable_permissions: u32) -> Self {
+        self.executable_permissions = executable_permissions;
+        self
+    }
+
+    /// Sets the permission bits of directory entries. The default is `0o755`.
+    pub fn directory_permissions(mut self, directory_permissions: u32) -> Self {
+        self.directory_permissions = directory_permissions;
+        self
+    }
+}
+
+impl Default for ReproducibleOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creates a reproducible zip archive that contains the files and directories from the specified
+/// directory: identical directory trees result in byte-identical archives, regardless of the file
+/// system, the modification times of the sources, and the current time. Entries are stored
+/// uncompressed, and stamped with the timestamp from the `SOURCE_DATE_EPOCH` environment
+/// variable, if it is set.
+pub fn zip_create_from_directory_reproducible(
+    archive_file: &Path,
+    directory: &Path,
+) -> ZipResult<()> {
+    let reproducible_options = ReproducibleOptions::from_source_date_epoch()?;
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let file = File::create(archive_file)?;
+    let mut zip_writer = ZipWriter::new(file);
+    zip_writer.create_from_directory_reproducible(
+        directory,
+        |_| options,
+        &DefaultEntryHandler,
+        &reproducible_options,
+    )?;
+    zip_writer.finish()?;
+    Ok(())
+}
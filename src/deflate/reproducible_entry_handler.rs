@@ -0,0 +1,79 @@
+use crate::default_entry_handler::DefaultEntryHandler;
+use crate::entry_handler::{EntryHandler, EntryOutcome};
+use crate::reproducible::ReproducibleOptions;
+use std::fs::Metadata;
+use std::io;
+use std::io::Write;
+use std::path::PathBuf;
+use zip::ZipWriter;
+use zip::result::ZipResult;
+use zip::write::{FileOptionExtension, FileOptions};
+
+/// An `EntryHandler` wrapper that normalizes the metadata of entries for reproducible archives.
+///
+/// The file options of every entry are stamped with the configured timestamp, and the permission
+/// bits are replaced with the configured file, executable or directory permissions before the
+/// entry is passed to the wrapped `inner` handler (see `EntryHandler` for how handlers are
+/// composed). Symbolic links keep the default permissions of the entry written by the inner
+/// handler.
+///
+/// Entries are only reproducible if the inner handler is; AES encryption, for example, uses a
+/// random salt for every entry.
+pub struct ReproducibleEntryHandler<H = DefaultEntryHandler> {
+    options: ReproducibleOptions,
+    inner: H,
+}
+
+impl ReproducibleEntryHandler<DefaultEntryHandler> {
+    pub fn new(options: ReproducibleOptions) -> Self {
+        Self::with_inner(DefaultEntryHandler, options)
+    }
+}
+
+impl<H> ReproducibleEntryHandler<H> {
+    pub fn with_inner(inner: H, options: ReproducibleOptions) -> Self {
+        Self { options, inner }
+    }
+
+    fn permissions(&self, metadata: &Metadata) -> u32 {
+        if metadata.is_dir() {
+            self.options.directory_permissions
+        } else if is_executable(metadata) {
+            self.options.executable_permissions
+        } else {
+            self.options.file_permissions
+        }
+    }
+}
+
+impl<T: FileOptionExtension, H> EntryHandler<T> for ReproducibleEntryHandler<H>
+where
+    H: EntryHandler<T>,
+{
+    fn handle_entry<W: Write + io::Seek>(
+        &self,
+        writer: &mut ZipWriter<W>,
+        root: &PathBuf,
+        entry_path: &PathBuf,
+        file_options: FileOptions<T>,
+    ) -> ZipResult<EntryOutcome> {
+        let mut file_options = file_options.last_modified_time(self.options.timestamp);
+        if !std::fs::symlink_metadata(entry_path)?.is_symlink() {
+            let metadata = std::fs::metadata(entry_path)?;
+            file_options = file_options.unix_permissions(self.permissions(&metadata));
+        }
+        self.inner
+            .handle_entry(writer, root, entry_path, file_options)
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o100 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &Metadata) -> bool {
+    false
+}
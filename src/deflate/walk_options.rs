@@ -0,0 +1,59 @@
+/// Decides how symbolic links are handled while a directory is walked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SymlinkPolicy {
+    /// Follows symbolic links to directories, and adds their entries. A link to one of its own
+    /// ancestors makes the walk loop until the archive exhausts the disk.
+    Follow,
+
+    /// Does not descend into symbolic links to directories; the links themselves are still passed
+    /// to the entry handler.
+    DontFollow,
+
+    /// Follows symbolic links to directories, unless the directory is one of its own ancestors.
+    /// Directories are identified by device and inode number on Unix platforms, and by their
+    /// canonical path elsewhere.
+    #[default]
+    FollowWithCycleDetection,
+}
+
+/// Options that control which entries `ZipWriterExtensions::create_from_directory_with_walk_options`
+/// visits below a directory.
+///
+/// The options follow the builder style used by `zip::write::FileOptions`; start from
+/// `WalkOptions::default()` and chain the setters of interest. The defaults match the behavior of
+/// `ZipWriterExtensions::create_from_directory_with_options`.
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    pub(crate) symlinks: SymlinkPolicy,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) same_file_system: bool,
+}
+
+impl WalkOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how symbolic links to directories are handled. The default is
+    /// `SymlinkPolicy::FollowWithCycleDetection`.
+    pub fn symlinks(mut self, symlinks: SymlinkPolicy) -> Self {
+        self.symlinks = symlinks;
+        self
+    }
+
+    /// Sets the maximum depth of visited entries, similar to `find -maxdepth`: the entries of the
+    /// directory itself have a depth of `1`, and directories at the maximum depth are visited, but
+    /// their entries are not. By default, the depth is not limited.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets whether the walk stays on the file system of the directory, similar to `find -xdev`.
+    /// The default is `false`. Directories on other file systems, such as mount points, are
+    /// visited, but their entries are not. Has no effect on non-Unix platforms.
+    pub fn same_file_system(mut self, same_file_system: bool) -> Self {
+        self.same_file_system = same_file_system;
+        self
+    }
+}
@@ -1,33 +1,47 @@
 use crate::default_entry_handler::DefaultEntryHandler;
-use crate::entry_handler::EntryHandler;
-use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use crate::entry_handler::{EntryHandler, EntryOutcome};
+use ignore::gitignore::{Gitignore, GitignoreBuilder, gitconfig_excludes_path};
 use std::collections::HashMap;
 use std::io;
-use std::io::Write;
+use std::io::{ErrorKind, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use zip::ZipWriter;
-use zip::result::ZipResult;
+use zip::result::{ZipError, ZipResult};
 use zip::write::{FileOptionExtension, FileOptions};
 
 /// An EntryHandler wrapper that honors `.zipignore` files similar to how `.gitignore` works.
 /// Patterns from `.zipignore` files are merged from the root directory down to deeper levels.
-/// If a path is not ignored, delegation continues to the wrapped `inner` handler.
+/// If a path is not ignored, delegation continues to the wrapped `inner` handler. Ignored
+/// directories are pruned, so their entries are not visited.
+///
+/// The names of the rule files can be configured, and the git ignore rules can be honored as
+/// well. When rules conflict, the last matching rule wins; rules are read in this order:
+///
+/// 1. the global git excludes file (`git_global`),
+/// 2. the `.git/info/exclude` file of the root directory (`git_exclude`),
+/// 3. for each directory from the root down to the entry, its `.gitignore` file (`git_ignore`),
+///    followed by its rule files in the order of `ignore_filenames`,
+/// 4. the patterns added with `patterns`.
 pub struct ZipIgnoreEntryHandler<H = DefaultEntryHandler> {
     per_directory_matcher_cache: Mutex<HashMap<PathBuf, Gitignore>>,
-    ignore_filename: &'static str,
+    ignore_filenames: Vec<String>,
+    git_ignore: bool,
+    git_exclude: bool,
+    /// The global git excludes file, resolved from the git configuration once `git_global` is set.
+    global_excludes_file: Option<PathBuf>,
+    patterns_builder: GitignoreBuilder,
+    patterns: Gitignore,
     inner: H,
 }
 
 pub(crate) const IGNORE_FILENAME: &str = ".zipignore";
 
+pub(crate) const GIT_IGNORE_FILENAME: &str = ".gitignore";
+
 impl ZipIgnoreEntryHandler<DefaultEntryHandler> {
     pub fn new() -> Self {
-        Self {
-            per_directory_matcher_cache: Mutex::new(HashMap::new()),
-            ignore_filename: IGNORE_FILENAME,
-            inner: DefaultEntryHandler,
-        }
+        Self::with_inner(DefaultEntryHandler)
     }
 }
 
@@ -35,17 +49,68 @@ impl<H> ZipIgnoreEntryHandler<H> {
     pub fn with_inner(inner: H) -> Self {
         Self {
             per_directory_matcher_cache: Mutex::new(HashMap::new()),
-            ignore_filename: IGNORE_FILENAME,
+            ignore_filenames: vec![IGNORE_FILENAME.to_string()],
+            git_ignore: false,
+            git_exclude: false,
+            global_excludes_file: None,
+            patterns_builder: GitignoreBuilder::new(""),
+            patterns: Gitignore::empty(),
             inner,
         }
     }
 
+    /// Sets the names of the rule files to honor, such as `[".zipignore", ".ignore"]`, in place
+    /// of `.zipignore`. If a directory has several of them, rules of later names take precedence.
+    pub fn ignore_filenames(mut self, filenames: &[&str]) -> Self {
+        self.ignore_filenames = filenames.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    /// Sets whether `.gitignore` files are honored. Defaults to `false`.
+    pub fn git_ignore(mut self, value: bool) -> Self {
+        self.git_ignore = value;
+        self
+    }
+
+    /// Sets whether the `.git/info/exclude` file is honored, if the root directory is the root of
+    /// a git repository. Defaults to `false`.
+    pub fn git_exclude(mut self, value: bool) -> Self {
+        self.git_exclude = value;
+        self
+    }
+
+    /// Sets whether the global git excludes file is honored; that is the `core.excludesFile` of
+    /// the git configuration, or `$XDG_CONFIG_HOME/git/ignore`. Defaults to `false`.
+    ///
+    /// The git configuration is read when this is set, not for every directory that is walked.
+    pub fn git_global(mut self, value: bool) -> Self {
+        self.global_excludes_file = if value {
+            gitconfig_excludes_path()
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Adds patterns, such as `target/` or `*.log`, that are matched relative to the root
+    /// directory, and take precedence over the rules of all files. Negated patterns (`!keep.txt`)
+    /// re-include entries.
+    pub fn patterns(mut self, patterns: &[&str]) -> ZipResult<Self> {
+        for pattern in patterns {
+            self.patterns_builder
+                .add_line(None, pattern)
+                .map_err(invalid_pattern)?;
+        }
+        self.patterns = self.patterns_builder.build().map_err(invalid_pattern)?;
+        Ok(self)
+    }
+
     fn parent_dir(path: &Path) -> &Path {
         path.parent().unwrap_or(path)
     }
 
     fn build_matcher(&self, root: &Path, dir: &Path) -> io::Result<Gitignore> {
-        // Build by adding all .zipignore files encountered from root to current dir
+        // Build by adding all rule files encountered from root to current dir
         let mut ignore_builder = GitignoreBuilder::new(root);
 
         // Collect directories from root to dir
@@ -63,10 +128,19 @@ impl<H> ZipIgnoreEntryHandler<H> {
         }
         stack.reverse();
 
+        if let Some(excludes_file) = &self.global_excludes_file {
+            Self::add_rules_file(&mut ignore_builder, excludes_file.clone());
+        }
+        if self.git_exclude {
+            let exclude_file = root.join(".git").join("info").join("exclude");
+            Self::add_rules_file(&mut ignore_builder, exclude_file);
+        }
         for d in stack {
-            let ignore_file = d.join(self.ignore_filename);
-            if ignore_file.exists() {
-                let _ = ignore_builder.add(ignore_file);
+            if self.git_ignore {
+                Self::add_rules_file(&mut ignore_builder, d.join(GIT_IGNORE_FILENAME));
+            }
+            for ignore_filename in &self.ignore_filenames {
+                Self::add_rules_file(&mut ignore_builder, d.join(ignore_filename));
             }
         }
         let built = ignore_builder
@@ -75,6 +149,12 @@ impl<H> ZipIgnoreEntryHandler<H> {
         Ok(built)
     }
 
+    fn add_rules_file(ignore_builder: &mut GitignoreBuilder, rules_file: PathBuf) {
+        if rules_file.is_file() {
+            let _ = ignore_builder.add(rules_file);
+        }
+    }
+
     fn matcher_for_dir(&self, root: &Path, dir: &Path) -> io::Result<Gitignore> {
         let mut cache = self.per_directory_matcher_cache.lock().unwrap();
         if let Some(existing_matcher) = cache.get(dir) {
@@ -91,6 +171,15 @@ impl<H> ZipIgnoreEntryHandler<H> {
         } else {
             Self::parent_dir(path)
         };
+        // The patterns are matched relative to the root, and take precedence over the rule files.
+        if let Ok(relative_path) = path.strip_prefix(root) {
+            let pattern_match = self
+                .patterns
+                .matched_path_or_any_parents(relative_path, is_dir);
+            if !pattern_match.is_none() {
+                return pattern_match.is_ignore();
+            }
+        }
         match self.matcher_for_dir(root, dir) {
             Ok(matcher) => matcher
                 .matched_path_or_any_parents(path, is_dir)
@@ -110,14 +199,22 @@ where
         root: &PathBuf,
         entry_path: &PathBuf,
         file_options: FileOptions<T>,
-        buffer: &mut Vec<u8>,
-    ) -> ZipResult<()> {
+    ) -> ZipResult<EntryOutcome> {
         let metadata = std::fs::metadata(entry_path)?;
         let is_dir = metadata.is_dir();
         if self.is_ignored(root.as_path(), entry_path.as_path(), is_dir) {
-            return Ok(());
+            // Entries of an ignored directory cannot be re-included, so its subtree is pruned.
+            return Ok(if is_dir {
+                EntryOutcome::Pruned
+            } else {
+                EntryOutcome::Ignored
+            });
         }
         self.inner
-            .handle_entry(writer, root, entry_path, file_options, buffer)
+            .handle_entry(writer, root, entry_path, file_options)
     }
 }
+
+fn invalid_pattern(error: ignore::Error) -> ZipError {
+    ZipError::Io(io::Error::new(ErrorKind::InvalidInput, error.to_string()))
+}
@@ -1,10 +1,16 @@
+use crate::create_observer::{CreateObserver, ObservingEntryHandler, notify_compressed_sizes};
 use crate::default_entry_handler::DefaultEntryHandler;
+use crate::deflate::directory_walk::DirectoryWalk;
+use crate::deflate::parallel_compression;
 use crate::deflate::zip_writer_extensions::ZipWriterExtensions;
-use crate::entry_handler::EntryHandler;
-use std::fs::File;
+use crate::entry_handler::{EntryHandler, EntryOutcome};
+use crate::reproducible::ReproducibleOptions;
+use crate::reproducible_entry_handler::ReproducibleEntryHandler;
+use crate::walk_options::WalkOptions;
+use std::fs::{File, OpenOptions};
 use std::io;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use zip::result::ZipResult;
 use zip::write::{FileOptionExtension, FileOptions, SimpleFileOptions};
 use zip::{CompressionMethod, ZipWriter};
@@ -36,6 +42,66 @@ where
     Ok(())
 }
 
+/// Creates a zip archive that contains the files and directories from the specified directory, and
+/// compresses up to `threads` entries concurrently; `0` uses one thread per available CPU. The
+/// directory is walked with the default `WalkOptions`; use
+/// `ZipWriterExtensions::create_from_directory_parallel` to change them.
+pub fn zip_create_from_directory_parallel<F, T>(
+    archive_file: &Path,
+    directory: &Path,
+    cb_file_options: F,
+    threads: usize,
+) -> ZipResult<()>
+where
+    T: FileOptionExtension,
+    F: Fn(&PathBuf) -> FileOptions<T> + Sync,
+{
+    let file = File::create(archive_file)?;
+    let mut zip_writer = ZipWriter::new(file);
+    zip_writer.create_from_directory_parallel(
+        directory,
+        cb_file_options,
+        &DefaultEntryHandler,
+        &WalkOptions::default(),
+        threads,
+    )?;
+    zip_writer.finish()?;
+    Ok(())
+}
+
+/// Creates a zip archive that contains the files and directories from the specified directory,
+/// and reports the progress to the given observer. Every visited entry is reported as it is
+/// handled by `handler`; once the archive is finished, the compressed size of each entry is
+/// reported as well.
+pub fn zip_create_from_directory_observed<F, T, H, O>(
+    archive_file: &PathBuf,
+    directory: &PathBuf,
+    cb_file_options: F,
+    handler: &H,
+    observer: &O,
+) -> ZipResult<()>
+where
+    T: FileOptionExtension,
+    F: Fn(&PathBuf) -> FileOptions<T>,
+    H: EntryHandler<T>,
+    O: CreateObserver,
+{
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(archive_file)?;
+    let mut zip_writer = ZipWriter::new(file);
+    zip_writer.create_from_directory_with_options(
+        directory,
+        cb_file_options,
+        &ObservingEntryHandler::new(handler, observer),
+    )?;
+    let mut archive = zip_writer.finish_into_readable()?;
+    notify_compressed_sizes(&mut archive, observer)
+}
+
 impl<W: Write + io::Seek> ZipWriterExtensions for ZipWriter<W> {
     fn create_from_directory(&mut self, directory: &PathBuf) -> ZipResult<()> {
         let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
@@ -53,24 +119,96 @@ impl<W: Write + io::Seek> ZipWriterExtensions for ZipWriter<W> {
         F: Fn(&PathBuf) -> FileOptions<T>,
         H: EntryHandler<T>,
     {
-        let mut paths_queue: Vec<PathBuf> = vec![];
-        paths_queue.push(directory.clone());
+        self.create_from_directory_with_walk_options(
+            directory,
+            cb_file_options,
+            handler,
+            &WalkOptions::default(),
+        )
+    }
 
-        let mut buffer = Vec::new();
+    fn create_from_directory_with_walk_options<F, T, H>(
+        &mut self,
+        directory: &Path,
+        cb_file_options: F,
+        handler: &H,
+        walk_options: &WalkOptions,
+    ) -> ZipResult<()>
+    where
+        T: FileOptionExtension,
+        F: Fn(&PathBuf) -> FileOptions<T>,
+        H: EntryHandler<T>,
+    {
+        let walk = DirectoryWalk::new(directory, walk_options);
+        add_entries(self, directory, walk, cb_file_options, handler)
+    }
 
-        while let Some(next) = paths_queue.pop() {
-            let directory_entry_iterator = std::fs::read_dir(next)?;
-            for entry in directory_entry_iterator {
-                let entry_path = entry?.path();
-                let file_options = cb_file_options(&entry_path);
-                handler.handle_entry(self, &directory, &entry_path, file_options, &mut buffer)?;
-                let entry_metadata = std::fs::metadata(entry_path.clone())?;
-                if entry_metadata.is_dir() {
-                    paths_queue.push(entry_path.clone());
-                }
-            }
-        }
+    fn create_from_directory_reproducible<F, T, H>(
+        &mut self,
+        directory: &Path,
+        cb_file_options: F,
+        handler: &H,
+        options: &ReproducibleOptions,
+    ) -> ZipResult<()>
+    where
+        T: FileOptionExtension,
+        F: Fn(&PathBuf) -> FileOptions<T>,
+        H: EntryHandler<T>,
+    {
+        let handler = ReproducibleEntryHandler::with_inner(handler, *options);
+        let walk = DirectoryWalk::sorted(directory, &WalkOptions::default());
+        add_entries(self, directory, walk, cb_file_options, &handler)
+    }
 
-        Ok(())
+    fn create_from_directory_parallel<F, T, H>(
+        &mut self,
+        directory: &Path,
+        cb_file_options: F,
+        handler: &H,
+        walk_options: &WalkOptions,
+        threads: usize,
+    ) -> ZipResult<()>
+    where
+        T: FileOptionExtension,
+        F: Fn(&PathBuf) -> FileOptions<T> + Sync,
+        H: EntryHandler<T> + Sync,
+    {
+        parallel_compression::create_from_directory_parallel(
+            self,
+            directory,
+            cb_file_options,
+            handler,
+            walk_options,
+            threads,
+        )
     }
 }
+
+/// Passes the entries of the walk to the handler, and skips the entries of directories that the
+/// handler pruned.
+fn add_entries<W, F, T, H>(
+    writer: &mut ZipWriter<W>,
+    directory: &Path,
+    mut walk: DirectoryWalk,
+    cb_file_options: F,
+    handler: &H,
+) -> ZipResult<()>
+where
+    W: Write + io::Seek,
+    T: FileOptionExtension,
+    F: Fn(&PathBuf) -> FileOptions<T>,
+    H: EntryHandler<T>,
+{
+    // `EntryHandler::handle_entry` takes the root directory as `&PathBuf`.
+    let directory = directory.to_path_buf();
+    while let Some(entry_path) = walk.next() {
+        let entry_path = entry_path?;
+        let file_options = cb_file_options(&entry_path);
+        let outcome = handler.handle_entry(writer, &directory, &entry_path, file_options)?;
+        if outcome == EntryOutcome::Pruned {
+            walk.prune(&entry_path);
+        }
+    }
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use crate::entry_handler::EntryHandler;
-use std::path::PathBuf;
+use crate::reproducible::ReproducibleOptions;
+use crate::walk_options::WalkOptions;
+use std::path::{Path, PathBuf};
 use zip::result::ZipResult;
 use zip::write::{FileOptionExtension, FileOptions};
 
@@ -8,6 +10,9 @@ pub trait ZipWriterExtensions {
     fn create_from_directory(&mut self, directory: &PathBuf) -> ZipResult<()>;
 
     /// Creates a zip archive that contains the files and directories from the specified directory, uses the specified compression level.
+    ///
+    /// Symbolic links to directories are followed, unless they point to one of their own
+    /// ancestors; use `create_from_directory_with_walk_options` to change this.
     fn create_from_directory_with_options<F, T, H>(
         &mut self,
         directory: &PathBuf,
@@ -18,4 +23,58 @@ pub trait ZipWriterExtensions {
         T: FileOptionExtension,
         F: Fn(&PathBuf) -> FileOptions<T>,
         H: EntryHandler<T>;
+
+    /// Creates a zip archive that contains the files and directories from the specified directory,
+    /// and decides with `walk_options` how symbolic links are followed, how deep the walk descends,
+    /// and whether it stays on the file system of the directory.
+    fn create_from_directory_with_walk_options<F, T, H>(
+        &mut self,
+        directory: &Path,
+        cb_file_options: F,
+        handler: &H,
+        walk_options: &WalkOptions,
+    ) -> ZipResult<()>
+    where
+        T: FileOptionExtension,
+        F: Fn(&PathBuf) -> FileOptions<T>,
+        H: EntryHandler<T>;
+
+    /// Creates a zip archive that contains the files and directories from the specified directory,
+    /// such that identical directory trees result in byte-identical archives on any machine.
+    ///
+    /// Entries are added in the byte order of their paths, instead of the order in which the file
+    /// system lists them, and their timestamps and permissions are normalized according to
+    /// `options` (see `ReproducibleEntryHandler`). The file options returned by `cb_file_options`
+    /// must not depend on the environment, such as the current time.
+    fn create_from_directory_reproducible<F, T, H>(
+        &mut self,
+        directory: &Path,
+        cb_file_options: F,
+        handler: &H,
+        options: &ReproducibleOptions,
+    ) -> ZipResult<()>
+    where
+        T: FileOptionExtension,
+        F: Fn(&PathBuf) -> FileOptions<T>,
+        H: EntryHandler<T>;
+
+    /// Creates a zip archive that contains the files and directories from the specified directory,
+    /// and compresses up to `threads` entries concurrently; `0` uses one thread per available CPU.
+    ///
+    /// Each entry is handled on a worker thread, which writes it to a temporary buffer; the
+    /// compressed entries are then copied into the archive in the same order as by
+    /// `create_from_directory_with_walk_options` with the same `walk_options`, so the archive
+    /// layout does not depend on the order in which the workers finish.
+    fn create_from_directory_parallel<F, T, H>(
+        &mut self,
+        directory: &Path,
+        cb_file_options: F,
+        handler: &H,
+        walk_options: &WalkOptions,
+        threads: usize,
+    ) -> ZipResult<()>
+    where
+        T: FileOptionExtension,
+        F: Fn(&PathBuf) -> FileOptions<T> + Sync,
+        H: EntryHandler<T> + Sync;
 }
@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Describes an entry that is about to be written to a path that already exists.
+#[derive(Debug, Clone)]
+pub struct Conflict<'a> {
+    /// The sanitized name of the entry, as stored in the archive.
+    pub name: &'a Path,
+
+    /// The existing path in the target directory.
+    pub path: &'a Path,
+
+    /// The modification time stored in the archive, if any.
+    pub entry_modified: Option<SystemTime>,
+
+    /// The modification time of the existing file, if it can be read.
+    pub existing_modified: Option<SystemTime>,
+}
+
+/// Decides a single conflict; returned by the callback of `ConflictStrategy::Ask`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Keeps the existing file, and skips the entry.
+    Skip,
+
+    /// Replaces the existing file with the entry.
+    Overwrite,
+
+    /// Writes the entry next to the existing file, as `name (1).txt`, `name (2).txt`, and so on.
+    Rename,
+
+    /// Stops the extraction with an `ErrorKind::AlreadyExists` error.
+    Fail,
+}
+
+/// A callback that decides what happens to an entry whose path already exists.
+pub type ConflictCallback = dyn Fn(&Conflict) -> ConflictResolution + Send + Sync;
+
+/// Decides what happens if an entry is about to be written to a path that already exists, such as
+/// a file that was in the target directory before, or an earlier entry with the same path.
+/// Existing directories are not conflicts; the entries of a directory entry are merged into them.
+///
+/// Skipped entries are listed in the `ExtractionReport` with `SkipReason::Conflict`.
+#[derive(Default)]
+pub enum ConflictStrategy {
+    /// Keeps existing files, and skips the entry.
+    Skip,
+
+    /// Replaces existing files.
+    #[default]
+    Overwrite,
+
+    /// Replaces existing files that were modified before the entry's modification time stored in
+    /// the archive; otherwise, the entry is skipped. Entries without a modification time are
+    /// skipped.
+    OverwriteIfNewer,
+
+    /// Keeps existing files, and writes the entry as `name (1).txt`, `name (2).txt`, and so on,
+    /// using the first name that does not exist.
+    Rename,
+
+    /// Stops the extraction with an `ErrorKind::AlreadyExists` error.
+    Fail,
+
+    /// Asks the callback for each conflict; use `ConflictStrategy::ask` to create it.
+    Ask(Box<ConflictCallback>),
+}
+
+impl ConflictStrategy {
+    /// Creates a strategy that asks the given callback for each conflict, such as to prompt the
+    /// user of an interactive tool.
+    pub fn ask<F>(callback: F) -> Self
+    where
+        F: Fn(&Conflict) -> ConflictResolution + Send + Sync + 'static,
+    {
+        Self::Ask(Box::new(callback))
+    }
+
+    pub(crate) fn resolve(&self, conflict: &Conflict) -> ConflictResolution {
+        match self {
+            Self::Skip => ConflictResolution::Skip,
+            Self::Overwrite => ConflictResolution::Overwrite,
+            Self::OverwriteIfNewer => match (conflict.entry_modified, conflict.existing_modified) {
+                (Some(entry_modified), Some(existing_modified))
+                    if entry_modified > existing_modified =>
+                {
+                    ConflictResolution::Overwrite
+                }
+                _ => ConflictResolution::Skip,
+            },
+            Self::Rename => ConflictResolution::Rename,
+            Self::Fail => ConflictResolution::Fail,
+            Self::Ask(callback) => callback(conflict),
+        }
+    }
+}
+
+/// Returns the first path of the form `name (n).ext` next to `path` that does not exist, and for
+/// which `reserved` returns `false`.
+pub(crate) fn renamed_path<F>(path: &Path, reserved: F) -> PathBuf
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut n: u64 = 1;
+    loop {
+        let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+        file_name.push(format!(" ({n})"));
+        if let Some(extension) = path.extension() {
+            file_name.push(".");
+            file_name.push(extension);
+        }
+        let candidate = path.with_file_name(file_name);
+        if std::fs::symlink_metadata(&candidate).is_err() && !reserved(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
@@ -0,0 +1,117 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use zip::result::{ZipError, ZipResult};
+
+/// Metadata of an archive entry that is passed to the predicate of an `ExtractFilter`.
+#[derive(Debug, Clone, Copy)]
+pub struct EntryMetadata<'a> {
+    /// The sanitized, relative path of the entry.
+    pub name: &'a Path,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+    /// The uncompressed size as declared in the archive.
+    pub size: u64,
+    pub compressed_size: u64,
+    pub unix_mode: Option<u32>,
+}
+
+/// A predicate over the metadata of an archive entry.
+pub type EntryPredicate = dyn Fn(&EntryMetadata) -> bool + Send + Sync;
+
+/// Decides which entries `ZipArchiveExtensions::extract_matching` extracts.
+///
+/// Patterns use the gitignore syntax also used by `.zipignore` files, so the same rule files work
+/// for creating and extracting archives. A pattern that matches a directory also matches all
+/// entries below it. An entry is extracted if it matches any include pattern (or no include
+/// patterns are set), matches no exclude pattern, and satisfies the predicate, if any.
+pub struct ExtractFilter {
+    include_builder: GitignoreBuilder,
+    include: Option<Gitignore>,
+    exclude_builder: GitignoreBuilder,
+    exclude: Option<Gitignore>,
+    predicate: Option<Box<EntryPredicate>>,
+}
+
+impl ExtractFilter {
+    /// Creates a filter that matches all entries.
+    pub fn new() -> Self {
+        Self {
+            include_builder: GitignoreBuilder::new(""),
+            include: None,
+            exclude_builder: GitignoreBuilder::new(""),
+            exclude: None,
+            predicate: None,
+        }
+    }
+
+    /// Adds patterns, such as `assets/**/*.png`, of entries to extract.
+    pub fn include(mut self, patterns: &[&str]) -> ZipResult<Self> {
+        self.include = Some(add_lines(&mut self.include_builder, patterns)?);
+        Ok(self)
+    }
+
+    /// Adds patterns of entries to leave out. Negated patterns (`!keep.txt`) re-include entries
+    /// excluded by earlier patterns.
+    pub fn exclude(mut self, patterns: &[&str]) -> ZipResult<Self> {
+        self.exclude = Some(add_lines(&mut self.exclude_builder, patterns)?);
+        Ok(self)
+    }
+
+    /// Adds the patterns of a gitignore-style rule file, such as a `.zipignore` file, as exclude
+    /// patterns.
+    pub fn exclude_from_file(self, rules_file: &Path) -> ZipResult<Self> {
+        let rules = std::fs::read_to_string(rules_file)?;
+        let patterns: Vec<&str> = rules.lines().collect();
+        self.exclude(&patterns)
+    }
+
+    /// Sets a predicate over the entry metadata that must hold for entries to be extracted.
+    pub fn predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&EntryMetadata) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Box::new(predicate));
+        self
+    }
+
+    /// Returns `true` if the entry is to be extracted.
+    pub fn matches(&self, entry: &EntryMetadata) -> bool {
+        if let Some(include) = &self.include
+            && !include
+                .matched_path_or_any_parents(entry.name, entry.is_dir)
+                .is_ignore()
+        {
+            return false;
+        }
+        if let Some(exclude) = &self.exclude
+            && exclude
+                .matched_path_or_any_parents(entry.name, entry.is_dir)
+                .is_ignore()
+        {
+            return false;
+        }
+        match &self.predicate {
+            Some(predicate) => predicate(entry),
+            None => true,
+        }
+    }
+}
+
+impl Default for ExtractFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn add_lines(builder: &mut GitignoreBuilder, patterns: &[&str]) -> ZipResult<Gitignore> {
+    for pattern in patterns {
+        builder.add_line(None, pattern).map_err(invalid_pattern)?;
+    }
+    builder.build().map_err(invalid_pattern)
+}
+
+fn invalid_pattern(error: ignore::Error) -> ZipError {
+    ZipError::Io(io::Error::new(ErrorKind::InvalidInput, error.to_string()))
+}
@@ -0,0 +1,180 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use zip::result::ZipError;
+
+/// Upper bounds that are enforced while an archive is extracted. A value of `None` disables the
+/// respective check.
+///
+/// Sizes are measured from the bytes actually produced by the decompressor, not from the sizes
+/// declared in the archive headers, which may be forged.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ExtractLimits {
+    pub(crate) max_entries: Option<usize>,
+    pub(crate) max_total_size: Option<u64>,
+    pub(crate) max_entry_size: Option<u64>,
+    pub(crate) max_compression_ratio: Option<f64>,
+    pub(crate) max_path_depth: Option<usize>,
+}
+
+impl ExtractLimits {
+    /// Sets the maximum number of entries an archive may contain.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Sets the maximum number of bytes that may be written to the target directory.
+    pub fn max_total_size(mut self, max_total_size: u64) -> Self {
+        self.max_total_size = Some(max_total_size);
+        self
+    }
+
+    /// Sets the maximum number of bytes a single entry may inflate to.
+    pub fn max_entry_size(mut self, max_entry_size: u64) -> Self {
+        self.max_entry_size = Some(max_entry_size);
+        self
+    }
+
+    /// Sets the maximum ratio between the inflated and the compressed size of an entry.
+    pub fn max_compression_ratio(mut self, max_compression_ratio: f64) -> Self {
+        self.max_compression_ratio = Some(max_compression_ratio);
+        self
+    }
+
+    /// Sets the maximum number of path components of an entry name.
+    pub fn max_path_depth(mut self, max_path_depth: usize) -> Self {
+        self.max_path_depth = Some(max_path_depth);
+        self
+    }
+
+    /// Returns the number of bytes an entry may inflate to, given its compressed size and the
+    /// number of bytes already written by the extraction.
+    pub(crate) fn entry_budget(&self, compressed_size: u64, total_size: u64) -> u64 {
+        let mut budget = u64::MAX;
+        if let Some(max_total_size) = self.max_total_size {
+            budget = budget.min(max_total_size.saturating_sub(total_size));
+        }
+        if let Some(max_entry_size) = self.max_entry_size {
+            budget = budget.min(max_entry_size);
+        }
+        if let Some(max_compression_ratio) = self.max_compression_ratio {
+            budget = budget.min(Self::ratio_budget(compressed_size, max_compression_ratio));
+        }
+        budget
+    }
+
+    /// Determines the limit that was exceeded by an entry that inflated to more bytes than its
+    /// budget allowed.
+    pub(crate) fn exceeded(
+        &self,
+        name: PathBuf,
+        total_size: u64,
+        entry_size: u64,
+    ) -> ExtractLimitError {
+        if let Some(max_total_size) = self.max_total_size
+            && total_size.saturating_add(entry_size) > max_total_size
+        {
+            return ExtractLimitError::TotalSizeExceeded {
+                limit: max_total_size,
+            };
+        }
+        if let Some(max_entry_size) = self.max_entry_size
+            && entry_size > max_entry_size
+        {
+            return ExtractLimitError::EntrySizeExceeded {
+                name,
+                limit: max_entry_size,
+            };
+        }
+        ExtractLimitError::CompressionRatioExceeded {
+            name,
+            limit: self.max_compression_ratio.unwrap_or_default(),
+        }
+    }
+
+    fn ratio_budget(compressed_size: u64, max_compression_ratio: f64) -> u64 {
+        let budget = compressed_size as f64 * max_compression_ratio;
+        if budget >= u64::MAX as f64 {
+            u64::MAX
+        } else {
+            budget as u64
+        }
+    }
+}
+
+/// The error raised when an extraction exceeds one of the configured `ExtractLimits`.
+///
+/// The error is returned as the source of a `ZipError::Io`; use `ExtractLimitError::from_error`
+/// to inspect it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtractLimitError {
+    /// The archive contains more entries than permitted.
+    TooManyEntries { limit: usize, actual: usize },
+
+    /// The total number of inflated bytes exceeds the permitted size.
+    TotalSizeExceeded { limit: u64 },
+
+    /// A single entry inflates to more bytes than permitted.
+    EntrySizeExceeded { name: PathBuf, limit: u64 },
+
+    /// The ratio between the inflated and compressed size of an entry exceeds the permitted
+    /// ratio.
+    CompressionRatioExceeded { name: PathBuf, limit: f64 },
+
+    /// The entry name has more path components than permitted.
+    PathTooDeep {
+        name: PathBuf,
+        limit: usize,
+        depth: usize,
+    },
+}
+
+impl ExtractLimitError {
+    /// Returns the limit error carried by the given error, if any.
+    pub fn from_error(error: &ZipError) -> Option<&Self> {
+        match error {
+            ZipError::Io(io_error) => io_error.get_ref()?.downcast_ref::<Self>(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ExtractLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyEntries { limit, actual } => write!(
+                f,
+                "The archive contains {actual} entries, which exceeds the limit of {limit}."
+            ),
+            Self::TotalSizeExceeded { limit } => write!(
+                f,
+                "The extracted data exceeds the maximum total size of {limit} bytes."
+            ),
+            Self::EntrySizeExceeded { name, limit } => write!(
+                f,
+                "The entry {} exceeds the maximum size of {limit} bytes.",
+                name.display()
+            ),
+            Self::CompressionRatioExceeded { name, limit } => write!(
+                f,
+                "The entry {} exceeds the maximum compression ratio of {limit}.",
+                name.display()
+            ),
+            Self::PathTooDeep { name, limit, depth } => write!(
+                f,
+                "The entry {} has {depth} path components, which exceeds the limit of {limit}.",
+                name.display()
+            ),
+        }
+    }
+}
+
+impl Error for ExtractLimitError {}
+
+impl From<ExtractLimitError> for ZipError {
+    fn from(error: ExtractLimitError) -> Self {
+        ZipError::Io(io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
@@ -0,0 +1,144 @@
+use crate::inflate::extraction_report::{ExtractionReport, SkipReason};
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+use zip::result::ZipError;
+
+/// Receives events while `ZipArchiveExtensions::extract_with_options` inflates an archive. All
+/// methods have empty default implementations, so observers only implement what they need.
+///
+/// Observers are shared with the extraction through `ExtractOptions::observer`; state must
+/// therefore be kept behind interior mutability.
+pub trait ExtractObserver: Send + Sync {
+    /// Called before the first entry is extracted. `total_uncompressed` is the sum of the sizes
+    /// declared in the archive, which is suitable for progress reporting but is not verified.
+    fn archive_started(&self, _entry_count: usize, _total_uncompressed: u64) {}
+
+    /// Called before an entry is written to the target directory.
+    fn entry_started(&self, _name: &Path, _uncompressed_size: u64) {}
+
+    /// Called repeatedly while the data of a file entry is written.
+    fn bytes_written(&self, _name: &Path, _bytes: u64) {}
+
+    /// Called after an entry has been written to the target directory.
+    fn entry_finished(&self, _name: &Path, _bytes_written: u64) {}
+
+    /// Called for entries that are not extracted.
+    fn entry_skipped(&self, _name: &Path, _reason: &SkipReason) {}
+
+    /// Called for entries whose extraction failed, if `ExtractOptions::continue_on_error` is set.
+    fn entry_failed(&self, _name: &Path, _error: &ZipError) {}
+
+    /// Called after all entries have been processed.
+    fn archive_finished(&self, _report: &ExtractionReport) {}
+}
+
+impl<O: ExtractObserver + ?Sized> ExtractObserver for Arc<O> {
+    fn archive_started(&self, entry_count: usize, total_uncompressed: u64) {
+        (**self).archive_started(entry_count, total_uncompressed)
+    }
+
+    fn entry_started(&self, name: &Path, uncompressed_size: u64) {
+        (**self).entry_started(name, uncompressed_size)
+    }
+
+    fn bytes_written(&self, name: &Path, bytes: u64) {
+        (**self).bytes_written(name, bytes)
+    }
+
+    fn entry_finished(&self, name: &Path, bytes_written: u64) {
+        (**self).entry_finished(name, bytes_written)
+    }
+
+    fn entry_skipped(&self, name: &Path, reason: &SkipReason) {
+        (**self).entry_skipped(name, reason)
+    }
+
+    fn entry_failed(&self, name: &Path, error: &ZipError) {
+        (**self).entry_failed(name, error)
+    }
+
+    fn archive_finished(&self, report: &ExtractionReport) {
+        (**self).archive_finished(report)
+    }
+}
+
+/// An `ExtractObserver` adapter that converts written bytes into a percentage of the archive's
+/// total uncompressed size. The callback is invoked whenever the percentage increases, and once
+/// with `100` when the extraction finishes.
+pub struct ProgressExtractObserver<F> {
+    callback: F,
+    total_uncompressed: AtomicU64,
+    written: AtomicU64,
+    last_percent: AtomicU8,
+}
+
+impl<F: Fn(u8) + Send + Sync> ProgressExtractObserver<F> {
+    pub fn new(callback: F) -> Self {
+        Self {
+            callback,
+            total_uncompressed: AtomicU64::new(0),
+            written: AtomicU64::new(0),
+            last_percent: AtomicU8::new(0),
+        }
+    }
+
+    fn report(&self, percent: u8) {
+        let previous = self.last_percent.fetch_max(percent, Ordering::Relaxed);
+        if percent > previous {
+            (self.callback)(percent);
+        }
+    }
+}
+
+impl<F: Fn(u8) + Send + Sync> ExtractObserver for ProgressExtractObserver<F> {
+    fn archive_started(&self, _entry_count: usize, total_uncompressed: u64) {
+        self.total_uncompressed
+            .store(total_uncompressed, Ordering::Relaxed);
+        self.written.store(0, Ordering::Relaxed);
+        self.last_percent.store(0, Ordering::Relaxed);
+    }
+
+    fn bytes_written(&self, _name: &Path, bytes: u64) {
+        let total = self.total_uncompressed.load(Ordering::Relaxed);
+        let written = self.written.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if total > 0 {
+            // Declared sizes may be wrong; never report 100 before the extraction has finished.
+            let percent = (written.min(total) as u128 * 100 / total as u128).min(99) as u8;
+            self.report(percent);
+        }
+    }
+
+    fn archive_finished(&self, _report: &ExtractionReport) {
+        self.report(100);
+    }
+}
+
+/// A reader that notifies an observer about the number of bytes read from the wrapped reader.
+pub(crate) struct ObservedReader<'a, R> {
+    inner: R,
+    name: &'a Path,
+    observer: &'a dyn ExtractObserver,
+}
+
+impl<'a, R: Read> ObservedReader<'a, R> {
+    pub(crate) fn new(inner: R, name: &'a Path, observer: &'a dyn ExtractObserver) -> Self {
+        Self {
+            inner,
+            name,
+            observer,
+        }
+    }
+}
+
+impl<R: Read> Read for ObservedReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        if bytes_read > 0 {
+            self.observer.bytes_written(self.name, bytes_read as u64);
+        }
+        Ok(bytes_read)
+    }
+}
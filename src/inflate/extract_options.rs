@@ -0,0 +1,225 @@
+use crate::inflate::conflict_strategy::ConflictStrategy;
+use crate::inflate::extract_filter::{EntryMetadata, ExtractFilter};
+use crate::inflate::extract_limits::ExtractLimits;
+use crate::inflate::extract_observer::ExtractObserver;
+use crate::inflate::extract_password::PasswordProvider;
+use crate::inflate::unix_permissions::DEFAULT_PERMISSIONS_MASK;
+use std::path::{Path, PathBuf};
+
+/// Options that control how `ZipArchiveExtensions::extract_with_options` inflates an archive.
+///
+/// The options follow the builder style used by `zip::write::FileOptions`; start from
+/// `ExtractOptions::default()` and chain the setters of interest. The defaults match the
+/// behavior of `ZipArchiveExtensions::extract`.
+pub struct ExtractOptions {
+    pub(crate) conflict_strategy: ConflictStrategy,
+    pub(crate) filter: Option<ExtractFilter>,
+    pub(crate) prefix: Option<PathBuf>,
+    pub(crate) strip_components: usize,
+    pub(crate) limits: ExtractLimits,
+    pub(crate) restore_symlinks: bool,
+    pub(crate) restore_permissions: bool,
+    pub(crate) permissions_mask: u32,
+    pub(crate) restore_timestamps: bool,
+    pub(crate) observer: Option<Box<dyn ExtractObserver>>,
+    pub(crate) atomic: bool,
+    pub(crate) keep_backup: bool,
+    pub(crate) password_provider: Option<Box<PasswordProvider>>,
+    pub(crate) continue_on_error: bool,
+}
+
+impl ExtractOptions {
+    pub fn new() -> Self {
+        Self {
+            conflict_strategy: ConflictStrategy::Overwrite,
+            filter: None,
+            prefix: None,
+            strip_components: 0,
+            limits: ExtractLimits::default(),
+            restore_symlinks: false,
+            restore_permissions: false,
+            permissions_mask: DEFAULT_PERMISSIONS_MASK,
+            restore_timestamps: false,
+            observer: None,
+            atomic: false,
+            keep_backup: false,
+            password_provider: None,
+            continue_on_error: false,
+        }
+    }
+
+    /// Sets whether existing files in the target directory are overwritten. The default is `true`.
+    /// Shorthand for `conflict_strategy` with `ConflictStrategy::Overwrite` or
+    /// `ConflictStrategy::Fail`.
+    pub fn overwrite(self, overwrite: bool) -> Self {
+        self.conflict_strategy(if overwrite {
+            ConflictStrategy::Overwrite
+        } else {
+            ConflictStrategy::Fail
+        })
+    }
+
+    /// Sets what happens if an entry is about to be written to a path that already exists. The
+    /// default is `ConflictStrategy::Overwrite`.
+    pub fn conflict_strategy(mut self, conflict_strategy: ConflictStrategy) -> Self {
+        self.conflict_strategy = conflict_strategy;
+        self
+    }
+
+    /// Sets a predicate over the sanitized, relative entry path that decides which entries are
+    /// extracted. Shorthand for `filter` with an `ExtractFilter` whose predicate checks the entry
+    /// name; replaces a filter set before.
+    pub fn entry_filter<F>(self, filter: F) -> Self
+    where
+        F: Fn(&Path) -> bool + Send + Sync + 'static,
+    {
+        self.filter(ExtractFilter::new().predicate(move |entry| filter(entry.name)))
+    }
+
+    /// Sets an `ExtractFilter` that decides which entries are extracted, based on glob patterns
+    /// or entry metadata. Replaces a filter set before, including one set with `entry_filter`.
+    pub fn filter(mut self, filter: ExtractFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Restricts the extraction to the entries below the given path prefix, such as
+    /// `project-1.2.3/docs`. The prefix is compared component-wise against the sanitized entry
+    /// path; other entries are skipped.
+    pub fn prefix<P: Into<PathBuf>>(mut self, prefix: P) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets the number of leading path components that are removed from entry paths before
+    /// entries are written, similar to `tar --strip-components`. The default is `0`. Entries
+    /// whose path has no more components than are removed are skipped.
+    ///
+    /// Components are removed after the entry path has been sanitized and `.` and `..` have been
+    /// resolved, so stripping cannot move an entry outside the target directory. Filters and
+    /// limits apply to the entry path as stored in the archive.
+    pub fn strip_components(mut self, strip_components: usize) -> Self {
+        self.strip_components = strip_components;
+        self
+    }
+
+    /// Sets the limits that are enforced during extraction.
+    pub fn limits(mut self, limits: ExtractLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets whether symlink entries are restored as symbolic links. The default is `false`, which
+    /// skips symlink entries.
+    ///
+    /// Links are only created if their target, resolved relative to the link's location, stays
+    /// inside the target directory; other links are skipped and listed in the returned
+    /// `ExtractionReport`.
+    pub fn restore_symlinks(mut self, restore_symlinks: bool) -> Self {
+        self.restore_symlinks = restore_symlinks;
+        self
+    }
+
+    /// Sets whether the Unix permission bits stored in the archive are applied to extracted files
+    /// and directories. The default is `false`, which creates them with the default permissions of
+    /// the process. Has no effect on non-Unix platforms.
+    ///
+    /// Restored modes may make files and directories read-only, so a later extraction into the
+    /// same directory can fail to replace them.
+    pub fn restore_permissions(mut self, restore_permissions: bool) -> Self {
+        self.restore_permissions = restore_permissions;
+        self
+    }
+
+    /// Sets the mask that is applied to restored permission bits. The default is
+    /// `DEFAULT_PERMISSIONS_MASK`, which strips the setuid, setgid and sticky bits.
+    pub fn permissions_mask(mut self, permissions_mask: u32) -> Self {
+        self.permissions_mask = permissions_mask;
+        self
+    }
+
+    /// Sets whether the modification and access times stored in the archive are applied to
+    /// extracted files and directories. The default is `false`, which leaves the current time.
+    ///
+    /// Times are read from the extended timestamp extra field, if present, or from the entry's
+    /// MS-DOS timestamp otherwise. MS-DOS timestamps carry no time zone; they are interpreted as
+    /// UTC, regardless of the time zone of the host, so that an archive restores the same times on
+    /// every host. Archives written by tools that store local time in them, without an extended
+    /// timestamp, are therefore restored shifted by the UTC offset of the host that wrote them.
+    pub fn restore_timestamps(mut self, restore_timestamps: bool) -> Self {
+        self.restore_timestamps = restore_timestamps;
+        self
+    }
+
+    /// Sets an observer that is notified about the progress of the extraction. Use
+    /// `ProgressExtractObserver` to receive percentages, or wrap the observer in an `Arc` to keep
+    /// access to its state.
+    pub fn observer<O>(mut self, observer: O) -> Self
+    where
+        O: ExtractObserver + 'static,
+    {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// Sets whether the archive is extracted atomically. The default is `false`, which writes
+    /// entries directly to the target directory, and may leave it partially populated if the
+    /// extraction fails.
+    ///
+    /// In atomic mode, the archive is extracted to a staging directory next to the target
+    /// directory, which is flushed to disk and then renamed to the target path; the target
+    /// directory does not need to exist. An existing target directory is replaced as a whole: it
+    /// is moved to a backup path as the staging directory takes its place, and removed afterward
+    /// unless `keep_backup` is set. On Linux, both directories are exchanged in a single step;
+    /// elsewhere, the target path does not exist for the short moment between two renames. If the
+    /// extraction fails, the staging directory is removed and the target directory is left
+    /// untouched.
+    pub fn atomic(mut self, atomic: bool) -> Self {
+        self.atomic = atomic;
+        self
+    }
+
+    /// Sets whether an atomic extraction keeps the replaced target directory. The default is
+    /// `false`. The backup path is returned in `ExtractionReport::backup`.
+    pub fn keep_backup(mut self, keep_backup: bool) -> Self {
+        self.keep_backup = keep_backup;
+        self
+    }
+
+    /// Sets the password that decrypts encrypted entries. Entries that are not encrypted are
+    /// extracted as usual. Without a password, extracting an encrypted entry fails with
+    /// `PasswordError::PasswordRequired`.
+    pub fn password<P: Into<Vec<u8>>>(self, password: P) -> Self {
+        let password = password.into();
+        self.password_provider(move |_| Some(password.clone()))
+    }
+
+    /// Sets a callback that supplies the password of each encrypted entry, for archives whose
+    /// entries are encrypted with different passwords. Replaces the `password`, if one is set.
+    pub fn password_provider<F>(mut self, provider: F) -> Self
+    where
+        F: Fn(&Path) -> Option<Vec<u8>> + Send + Sync + 'static,
+    {
+        self.password_provider = Some(Box::new(provider));
+        self
+    }
+
+    /// Sets whether the extraction continues if an entry cannot be extracted, such as an entry
+    /// with corrupt data or a wrong password. The default is `false`, which stops at the first
+    /// error. Failed entries are listed in `ExtractionReport::failed`; partially written files are
+    /// removed. Exceeded limits always stop the extraction.
+    pub fn continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    pub(crate) fn accepts(&self, entry: &EntryMetadata) -> bool {
+        self.filter.as_ref().is_none_or(|f| f.matches(entry))
+    }
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,136 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+use zip::read::ZipFile;
+use zip::result::{ZipError, ZipResult};
+
+/// Supplies the password of an encrypted entry, given the entry name as stored in the archive.
+/// Returning `None` leaves the entry encrypted, which fails the extraction with
+/// `PasswordError::PasswordRequired`.
+pub type PasswordProvider = dyn Fn(&Path) -> Option<Vec<u8>> + Send + Sync;
+
+/// The header ID of the AES extra field (WinZip AE-x).
+const AES_EXTRA_FIELD: u16 = 0x9901;
+
+/// The header ID of the extra field used by PKWARE strong encryption.
+const STRONG_ENCRYPTION_EXTRA_FIELD: u16 = 0x0017;
+
+/// The error raised when an encrypted entry cannot be decrypted.
+///
+/// The error is returned as the source of a `ZipError::Io`; use `PasswordError::from_error` to
+/// inspect it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordError {
+    /// The entry is encrypted, but no password was supplied for it.
+    PasswordRequired { name: PathBuf },
+
+    /// The supplied password does not decrypt the entry.
+    WrongPassword { name: PathBuf },
+
+    /// The entry is encrypted with a method that cannot be decrypted, such as PKWARE strong
+    /// encryption, or AES if the `aes-crypto` feature is disabled.
+    UnsupportedEncryption { name: PathBuf },
+}
+
+impl PasswordError {
+    /// Returns the password error carried by the given error, if any.
+    pub fn from_error(error: &ZipError) -> Option<&Self> {
+        match error {
+            ZipError::Io(io_error) => io_error.get_ref()?.downcast_ref::<Self>(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PasswordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PasswordRequired { name } => {
+                write!(f, "The entry {} requires a password.", name.display())
+            }
+            Self::WrongPassword { name } => write!(
+                f,
+                "The password does not decrypt the entry {}.",
+                name.display()
+            ),
+            Self::UnsupportedEncryption { name } => write!(
+                f,
+                "The entry {} uses an unsupported encryption method.",
+                name.display()
+            ),
+        }
+    }
+}
+
+impl Error for PasswordError {}
+
+impl From<PasswordError> for ZipError {
+    fn from(error: PasswordError) -> Self {
+        let kind = match error {
+            PasswordError::PasswordRequired { .. } => io::ErrorKind::PermissionDenied,
+            PasswordError::WrongPassword { .. } => io::ErrorKind::InvalidInput,
+            PasswordError::UnsupportedEncryption { .. } => io::ErrorKind::Unsupported,
+        };
+        ZipError::Io(io::Error::new(kind, error))
+    }
+}
+
+/// Opens an entry for reading. Encrypted entries are decrypted with the password returned by
+/// `password_for`, which is only called for encrypted entries.
+pub(crate) fn open_entry<R, F>(
+    archive: &mut ZipArchive<R>,
+    file_number: usize,
+    password_for: F,
+) -> ZipResult<ZipFile<'_, R>>
+where
+    R: Read + Seek,
+    F: FnOnce(&Path) -> Option<Vec<u8>>,
+{
+    let (name, supported) = {
+        let entry = archive.by_index_raw(file_number)?;
+        if !entry.encrypted() {
+            (None, true)
+        } else {
+            (
+                Some(PathBuf::from(entry.name())),
+                is_supported_encryption(entry.extra_data().unwrap_or_default()),
+            )
+        }
+    };
+    let name = match name {
+        Some(name) => name,
+        None => return archive.by_index(file_number),
+    };
+    if !supported {
+        return Err(PasswordError::UnsupportedEncryption { name }.into());
+    }
+    let password = match password_for(&name) {
+        Some(password) => password,
+        None => return Err(PasswordError::PasswordRequired { name }.into()),
+    };
+    archive
+        .by_index_decrypt(file_number, &password)
+        .map_err(|error| match error {
+            ZipError::InvalidPassword => PasswordError::WrongPassword { name }.into(),
+            error => error,
+        })
+}
+
+/// Checks the extra fields of an encrypted entry for encryption methods that cannot be decrypted.
+fn is_supported_encryption(extra_data: &[u8]) -> bool {
+    let mut fields = extra_data;
+    while fields.len() >= 4 {
+        let header_id = u16::from_le_bytes([fields[0], fields[1]]);
+        let size = usize::from(u16::from_le_bytes([fields[2], fields[3]]));
+        match header_id {
+            STRONG_ENCRYPTION_EXTRA_FIELD => return false,
+            AES_EXTRA_FIELD if !cfg!(feature = "aes-crypto") => return false,
+            _ => {}
+        }
+        fields = fields.get(4 + size..).unwrap_or_default();
+    }
+    true
+}
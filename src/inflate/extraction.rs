@@ -0,0 +1,598 @@
+use crate::file_utils::{TEMP_FILE_PREFIX, create_temp_file};
+use crate::inflate::conflict_strategy::{Conflict, ConflictResolution, renamed_path};
+use crate::inflate::extract_filter::EntryMetadata;
+use crate::inflate::extract_limits::ExtractLimitError;
+use crate::inflate::extract_observer::{ExtractObserver, ObservedReader};
+use crate::inflate::extract_options::ExtractOptions;
+use crate::inflate::extract_password;
+use crate::inflate::extraction_report::{EntryKind, ExtractionReport, SkipReason};
+use crate::inflate::file_times::EntryTimes;
+use crate::inflate::subtree;
+use crate::inflate::symlinks;
+use crate::inflate::unix_permissions::apply_unix_mode;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io;
+use std::io::{ErrorKind, Read, Seek};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use zip::ZipArchive;
+use zip::read::ZipFile;
+use zip::result::{ZipError, ZipResult};
+
+/// Metadata of a directory entry that is applied after all entries have been extracted.
+struct DeferredDirectory {
+    path: PathBuf,
+    mode: Option<u32>,
+    times: Option<EntryTimes>,
+}
+
+/// A symbolic link restored by the extraction, whose target is checked again once all links
+/// exist.
+struct RestoredSymlink {
+    name: PathBuf,
+    path: PathBuf,
+    target: PathBuf,
+}
+
+/// An entry that passed sanitization, filters and path limits, and is about to be extracted.
+#[derive(Clone)]
+pub(crate) struct PlannedEntry {
+    pub(crate) sanitized_name: PathBuf,
+    pub(crate) output_path: PathBuf,
+    pub(crate) kind: EntryKind,
+    /// Whether a conflict with an existing file was already resolved (see
+    /// `Extraction::resolve_planned_conflict`).
+    pub(crate) conflict_resolved: bool,
+}
+
+/// The state of a single `extract_with_options` run. Entries are passed in one by one; `finish`
+/// applies deferred directory metadata and returns the report.
+///
+/// All methods take `&self`, so that the entries of an archive can be extracted by several
+/// threads at once.
+pub(crate) struct Extraction<'a> {
+    root: PathBuf,
+    options: &'a ExtractOptions,
+    report: Mutex<ExtractionReport>,
+    total_size: AtomicU64,
+    directories: Mutex<Vec<DeferredDirectory>>,
+    created_paths: Mutex<Vec<PathBuf>>,
+    symlinks: Mutex<Vec<RestoredSymlink>>,
+}
+
+impl<'a> Extraction<'a> {
+    /// Starts an extraction into `root`, which must be an existing, canonicalized directory.
+    pub(crate) fn new(root: PathBuf, options: &'a ExtractOptions) -> Self {
+        Self {
+            root,
+            options,
+            report: Mutex::new(ExtractionReport::new()),
+            total_size: AtomicU64::new(0),
+            directories: Mutex::new(Vec::new()),
+            created_paths: Mutex::new(Vec::new()),
+            symlinks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Notifies the observer that the extraction of `entry_count` entries starts.
+    pub(crate) fn start(&self, entry_count: usize, total_uncompressed: u64) {
+        if let Some(observer) = self.observer() {
+            observer.archive_started(entry_count, total_uncompressed);
+        }
+    }
+
+    /// Returns `true` if an observer is registered with the options.
+    pub(crate) fn is_observed(&self) -> bool {
+        self.options.observer.is_some()
+    }
+
+    /// Checks the entry count of the archive against the configured limit.
+    pub(crate) fn check_entry_count(&self, entry_count: usize) -> ZipResult<()> {
+        match self.options.limits.max_entries {
+            Some(limit) if entry_count > limit => Err(ExtractLimitError::TooManyEntries {
+                limit,
+                actual: entry_count,
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Opens an entry of the archive for reading, and decrypts it with the configured password if
+    /// it is encrypted.
+    pub(crate) fn open_entry<'r, R: Read + Seek>(
+        &self,
+        archive: &'r mut ZipArchive<R>,
+        file_number: usize,
+    ) -> ZipResult<ZipFile<'r, R>> {
+        extract_password::open_entry(archive, file_number, |name| {
+            self.options
+                .password_provider
+                .as_ref()
+                .and_then(|provider| provider(name))
+        })
+    }
+
+    /// Sanitizes the entry name and applies filters and path limits. Returns `None` for entries
+    /// that are skipped; those are recorded in the report. Only the metadata of the entry is
+    /// read, so raw entries can be planned as well.
+    pub(crate) fn plan_entry<R: Read>(
+        &self,
+        entry: &ZipFile<R>,
+    ) -> ZipResult<Option<PlannedEntry>> {
+        let sanitized_name = match entry.enclosed_name() {
+            Some(name) => name,
+            None => {
+                self.skip(PathBuf::from(entry.name()), SkipReason::UnsafePath);
+                return Ok(None);
+            }
+        };
+        let metadata = EntryMetadata {
+            name: &sanitized_name,
+            is_dir: entry.is_dir(),
+            is_symlink: entry.is_symlink(),
+            size: entry.size(),
+            compressed_size: entry.compressed_size(),
+            unix_mode: entry.unix_mode(),
+        };
+        if !self.options.accepts(&metadata) {
+            self.skip(sanitized_name, SkipReason::Filtered);
+            return Ok(None);
+        }
+        if let Some(limit) = self.options.limits.max_path_depth {
+            let depth = sanitized_name.components().count();
+            if depth > limit {
+                return Err(ExtractLimitError::PathTooDeep {
+                    name: sanitized_name,
+                    limit,
+                    depth,
+                }
+                .into());
+            }
+        }
+
+        let output_path = match subtree::relocate(
+            &sanitized_name,
+            self.options.prefix.as_deref(),
+            self.options.strip_components,
+        ) {
+            Some(relative_path) => self.root.join(relative_path),
+            None => {
+                self.skip(sanitized_name, SkipReason::OutsideSubtree);
+                return Ok(None);
+            }
+        };
+
+        let kind = if entry.is_dir() {
+            EntryKind::Directory
+        } else if entry.is_file() {
+            EntryKind::File
+        } else if entry.is_symlink() {
+            EntryKind::Symlink
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(PlannedEntry {
+            sanitized_name,
+            output_path,
+            kind,
+            conflict_resolved: false,
+        }))
+    }
+
+    /// Writes a planned entry to the target directory.
+    pub(crate) fn extract_planned<R: Read>(
+        &self,
+        entry: &mut ZipFile<R>,
+        planned: PlannedEntry,
+    ) -> ZipResult<()> {
+        if let Some(observer) = self.observer() {
+            observer.entry_started(&planned.sanitized_name, entry.size());
+        }
+        let PlannedEntry {
+            sanitized_name,
+            output_path,
+            kind,
+            conflict_resolved,
+        } = planned;
+        match kind {
+            EntryKind::Directory => self.extract_directory(entry, sanitized_name, output_path),
+            EntryKind::File => {
+                self.extract_file(entry, sanitized_name, output_path, conflict_resolved)
+            }
+            EntryKind::Symlink => self.extract_symlink(entry, sanitized_name, output_path),
+        }
+    }
+
+    /// Creates the parent directory of a planned entry ahead of its extraction.
+    pub(crate) fn create_parent(&self, planned: &PlannedEntry) -> ZipResult<()> {
+        match planned.output_path.parent() {
+            Some(parent) => self.create_dir_all(parent),
+            None => Ok(()),
+        }
+    }
+
+    /// Applies the conflict strategy to a planned entry ahead of its extraction, so that the
+    /// conflicts of entries that are extracted concurrently are decided in archive order. A
+    /// renamed entry avoids the paths in `reserved`, and its new path is added to them. Returns
+    /// `false` if the entry is skipped.
+    pub(crate) fn resolve_planned_conflict<R: Read>(
+        &self,
+        entry: &ZipFile<R>,
+        planned: &mut PlannedEntry,
+        reserved: &mut HashSet<PathBuf>,
+    ) -> ZipResult<bool> {
+        let output_path = match self.resolve_conflict(
+            entry,
+            &planned.sanitized_name,
+            planned.output_path.clone(),
+            |candidate| reserved.contains(candidate),
+        )? {
+            Some(output_path) => output_path,
+            None => return Ok(false),
+        };
+        if output_path != planned.output_path {
+            reserved.insert(output_path.clone());
+            planned.output_path = output_path;
+        }
+        planned.conflict_resolved = true;
+        Ok(true)
+    }
+
+    /// Handles an error that stopped the extraction. Restored links that escape the root are
+    /// removed. If a limit was exceeded, everything created by this extraction so far is removed;
+    /// files that existed before the extraction started are left in place. An entry that exceeds
+    /// a limit never replaces an existing file, since files are written to a temporary file
+    /// first, but entries extracted before it may have.
+    pub(crate) fn abort(&self, error: ZipError) -> ZipError {
+        self.remove_escaping_symlinks();
+        if ExtractLimitError::from_error(&error).is_some() {
+            let mut created_paths = self.created_paths.lock().unwrap();
+            for path in created_paths.drain(..).rev() {
+                let is_dir = std::fs::symlink_metadata(&path).is_ok_and(|m| m.is_dir());
+                let _ = if is_dir {
+                    std::fs::remove_dir(&path)
+                } else {
+                    std::fs::remove_file(&path)
+                };
+            }
+            self.directories.lock().unwrap().clear();
+        }
+        error
+    }
+
+    /// Handles an error that stopped the extraction of a single entry. If
+    /// `ExtractOptions::continue_on_error` is set, the error is recorded in the report and the
+    /// extraction continues with the next entry; exceeded limits always stop the extraction.
+    pub(crate) fn fail_entry(&self, name: PathBuf, error: ZipError) -> ZipResult<()> {
+        if !self.options.continue_on_error || ExtractLimitError::from_error(&error).is_some() {
+            return Err(error);
+        }
+        if let Some(observer) = self.observer() {
+            observer.entry_failed(&name, &error);
+        }
+        self.report.lock().unwrap().trace_failed(name, &error);
+        Ok(())
+    }
+
+    /// Applies deferred directory metadata and returns the report.
+    pub(crate) fn finish(self) -> ZipResult<ExtractionReport> {
+        self.remove_escaping_symlinks();
+        // Directory metadata is applied last, deepest first, so that writing children does not
+        // update restored times, and read-only directories do not prevent children from being
+        // written.
+        let mut directories = self.directories.into_inner().unwrap();
+        directories.sort_by(|a, b| b.path.cmp(&a.path));
+        for directory in &directories {
+            if let Some(times) = directory.times {
+                times.apply(&directory.path)?;
+            }
+            if let Some(mode) = directory.mode {
+                apply_unix_mode(&directory.path, mode, self.options.permissions_mask)?;
+            }
+        }
+        let report = self.report.into_inner().unwrap();
+        if let Some(observer) = self.options.observer.as_deref() {
+            observer.archive_finished(&report);
+        }
+        Ok(report)
+    }
+
+    fn extract_directory<R: Read>(
+        &self,
+        entry: &mut ZipFile<R>,
+        sanitized_name: PathBuf,
+        extracted_folder_path: PathBuf,
+    ) -> ZipResult<()> {
+        self.create_dir_all(&extracted_folder_path)?;
+        self.entry_finished(
+            sanitized_name,
+            &extracted_folder_path,
+            EntryKind::Directory,
+            0,
+        );
+        self.directories.lock().unwrap().push(DeferredDirectory {
+            path: extracted_folder_path,
+            mode: entry
+                .unix_mode()
+                .filter(|_| self.options.restore_permissions),
+            times: EntryTimes::from_entry(entry).filter(|_| self.options.restore_timestamps),
+        });
+        Ok(())
+    }
+
+    fn extract_file<R: Read>(
+        &self,
+        entry: &mut ZipFile<R>,
+        sanitized_name: PathBuf,
+        extracted_file_path: PathBuf,
+        conflict_resolved: bool,
+    ) -> ZipResult<()> {
+        if let Some(parent) = extracted_file_path.parent() {
+            self.create_dir_all(parent)?;
+            // The parent may have been replaced by a link since it was created, so it is resolved
+            // again right before the file is written.
+            self.ensure_within_root(parent)?;
+        }
+        let extracted_file_path = if conflict_resolved {
+            extracted_file_path
+        } else {
+            match self.resolve_conflict(entry, &sanitized_name, extracted_file_path, |_| false)? {
+                Some(path) => path,
+                None => return Ok(()),
+            }
+        };
+        let existed = std::fs::symlink_metadata(&extracted_file_path).is_ok();
+
+        // The entry is written to a temporary file next to the target, which replaces the target
+        // only once the limits are checked, so that an existing file is never left partially
+        // overwritten, and partially written data cannot be mistaken for the entry's content.
+        // The name of the temporary file does not include the name of the target, so that it is
+        // no longer than the longest name the file system accepts.
+        let directory = extracted_file_path.parent().unwrap_or(&self.root);
+        let (mut file, temp_path) = create_temp_file(directory, TEMP_FILE_PREFIX)?;
+        let written = self.write_file(entry, &sanitized_name, &mut file);
+        drop(file);
+        let bytes_written = match written.and_then(|bytes_written| {
+            std::fs::rename(&temp_path, &extracted_file_path)?;
+            Ok(bytes_written)
+        }) {
+            Ok(bytes_written) => bytes_written,
+            Err(error) => {
+                let _ = std::fs::remove_file(&temp_path);
+                return Err(error);
+            }
+        };
+        if !existed {
+            self.track_created(extracted_file_path.clone());
+        }
+
+        if let Some(times) = EntryTimes::from_entry(entry)
+            && self.options.restore_timestamps
+        {
+            times.apply(&extracted_file_path)?;
+        }
+        if let Some(mode) = entry.unix_mode()
+            && self.options.restore_permissions
+        {
+            apply_unix_mode(&extracted_file_path, mode, self.options.permissions_mask)?;
+        }
+        self.entry_finished(
+            sanitized_name,
+            &extracted_file_path,
+            EntryKind::File,
+            bytes_written,
+        );
+        Ok(())
+    }
+
+    /// Copies the data of a file entry to `file`, and checks the entry against the limits. Returns
+    /// the number of bytes written.
+    fn write_file<R: Read>(
+        &self,
+        entry: &mut ZipFile<R>,
+        sanitized_name: &Path,
+        file: &mut File,
+    ) -> ZipResult<u64> {
+        // Read at most one byte more than the budget, so that exceeding a limit is detected
+        // without inflating the rest of the entry.
+        let limits = self.options.limits;
+        let total_size = self.total_size.load(Ordering::SeqCst);
+        let budget = limits.entry_budget(entry.compressed_size(), total_size);
+        let mut reader = entry.take(budget.saturating_add(1));
+        let bytes_written = match self.observer() {
+            Some(observer) => io::copy(
+                &mut ObservedReader::new(reader, sanitized_name, observer),
+                file,
+            )?,
+            None => io::copy(&mut reader, file)?,
+        };
+        if bytes_written > budget {
+            return Err(limits
+                .exceeded(sanitized_name.to_path_buf(), total_size, bytes_written)
+                .into());
+        }
+        // Entries extracted concurrently share the total budget; the sum is checked again once
+        // their sizes are known.
+        let total_size = self.total_size.fetch_add(bytes_written, Ordering::SeqCst);
+        if let Some(limit) = limits.max_total_size
+            && total_size.saturating_add(bytes_written) > limit
+        {
+            return Err(ExtractLimitError::TotalSizeExceeded { limit }.into());
+        }
+        Ok(bytes_written)
+    }
+
+    fn extract_symlink<R: Read>(
+        &self,
+        entry: &mut ZipFile<R>,
+        sanitized_name: PathBuf,
+        extracted_link_path: PathBuf,
+    ) -> ZipResult<()> {
+        if !self.options.restore_symlinks || !symlinks::symlinks_supported() {
+            self.skip(sanitized_name, SkipReason::Symlink);
+            return Ok(());
+        }
+        if let Some(parent) = extracted_link_path.parent() {
+            self.create_dir_all(parent)?;
+        }
+        let extracted_link_path =
+            match self.resolve_conflict(entry, &sanitized_name, extracted_link_path, |_| false)? {
+                Some(path) => path,
+                None => return Ok(()),
+            };
+        let link_target = symlinks::read_symlink_target(entry)?;
+        let created = symlinks::restore_symlink(&self.root, &extracted_link_path, &link_target)?;
+        if created {
+            self.entry_finished(
+                sanitized_name.clone(),
+                &extracted_link_path,
+                EntryKind::Symlink,
+                0,
+            );
+            self.track_created(extracted_link_path.clone());
+            self.symlinks.lock().unwrap().push(RestoredSymlink {
+                name: sanitized_name,
+                path: extracted_link_path,
+                target: link_target,
+            });
+        } else {
+            self.skip(
+                sanitized_name,
+                SkipReason::SymlinkEscapesRoot {
+                    target: link_target,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Applies the conflict strategy if `output_path` already exists. Returns the path the entry is
+    /// written to, or `None` if the entry is skipped. Renamed entries avoid the paths for which
+    /// `reserved` returns `true`.
+    fn resolve_conflict<R: Read, F: Fn(&Path) -> bool>(
+        &self,
+        entry: &ZipFile<R>,
+        sanitized_name: &Path,
+        output_path: PathBuf,
+        reserved: F,
+    ) -> ZipResult<Option<PathBuf>> {
+        let existing = match std::fs::symlink_metadata(&output_path) {
+            Ok(existing) => existing,
+            Err(_) => return Ok(Some(output_path)),
+        };
+        let conflict = Conflict {
+            name: sanitized_name,
+            path: &output_path,
+            entry_modified: EntryTimes::from_entry(entry).map(|times| times.modified),
+            existing_modified: existing.modified().ok(),
+        };
+        match self.options.conflict_strategy.resolve(&conflict) {
+            ConflictResolution::Overwrite => Ok(Some(output_path)),
+            ConflictResolution::Rename => Ok(Some(renamed_path(&output_path, reserved))),
+            ConflictResolution::Skip => {
+                self.skip(sanitized_name.to_path_buf(), SkipReason::Conflict);
+                Ok(None)
+            }
+            ConflictResolution::Fail => Err(ZipError::Io(io::Error::new(
+                ErrorKind::AlreadyExists,
+                "The specified file already exists.",
+            ))),
+        }
+    }
+
+    fn observer(&self) -> Option<&'a dyn ExtractObserver> {
+        self.options.observer.as_deref()
+    }
+
+    /// Records a written entry in the report and notifies the observer.
+    fn entry_finished(&self, name: PathBuf, path: &Path, kind: EntryKind, bytes_written: u64) {
+        if let Some(observer) = self.observer() {
+            observer.entry_finished(&name, bytes_written);
+        }
+        let relative_path = path.strip_prefix(&self.root).unwrap_or(path).to_path_buf();
+        self.report
+            .lock()
+            .unwrap()
+            .trace_written(name, relative_path, kind, bytes_written);
+    }
+
+    /// Records a skipped entry in the report and notifies the observer.
+    pub(crate) fn skip(&self, name: PathBuf, reason: SkipReason) {
+        if let Some(observer) = self.observer() {
+            observer.entry_skipped(&name, &reason);
+        }
+        self.report.lock().unwrap().trace_skipped(name, reason);
+    }
+
+    /// Removes restored links whose targets escape the root because of links that were restored
+    /// after them, and reports them as skipped. Removing a link can change how other links
+    /// resolve, so the links are checked until none escapes.
+    fn remove_escaping_symlinks(&self) {
+        let mut restored = self.symlinks.lock().unwrap();
+        while let Some(index) = restored.iter().position(|link| {
+            matches!(
+                symlinks::resolve_within_root(&self.root, &link.path, &link.target),
+                Ok(None)
+            )
+        }) {
+            let link = restored.swap_remove(index);
+            let parent_within_root = link
+                .path
+                .parent()
+                .and_then(|parent| parent.canonicalize().ok())
+                .is_some_and(|parent| parent.starts_with(&self.root));
+            if parent_within_root
+                && std::fs::symlink_metadata(&link.path).is_ok_and(|m| m.is_symlink())
+            {
+                let _ = std::fs::remove_file(&link.path);
+            }
+            self.report.lock().unwrap().retract_written(&link.name);
+            self.skip(
+                link.name,
+                SkipReason::SymlinkEscapesRoot {
+                    target: link.target,
+                },
+            );
+        }
+    }
+
+    /// Fails if `path` resolves to a location outside of the target directory, such as through a
+    /// link restored earlier in the extraction.
+    fn ensure_within_root(&self, path: &Path) -> ZipResult<()> {
+        match symlinks::resolve_path(path)? {
+            Some(resolved) if resolved.starts_with(&self.root) => Ok(()),
+            _ => Err(ZipError::Io(io::Error::new(
+                ErrorKind::InvalidInput,
+                "The entry resolves to a location outside of the target directory.",
+            ))),
+        }
+    }
+
+    fn track_created(&self, path: PathBuf) {
+        self.created_paths.lock().unwrap().push(path);
+    }
+
+    /// Creates a directory and all of its missing parents, and remembers the ones that did not
+    /// exist before.
+    fn create_dir_all(&self, path: &Path) -> ZipResult<()> {
+        self.ensure_within_root(path)?;
+        let mut missing: Vec<PathBuf> = Vec::new();
+        let mut current = Some(path);
+        while let Some(directory) = current {
+            if directory.exists() {
+                break;
+            }
+            missing.push(directory.to_path_buf());
+            current = directory.parent();
+        }
+        std::fs::create_dir_all(path)?;
+        self.created_paths
+            .lock()
+            .unwrap()
+            .extend(missing.into_iter().rev());
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+use zip::result::ZipError;
+
+/// Summarizes the outcome of `ZipArchiveExtensions::extract_with_options`.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractionReport {
+    /// The directories, files and symlinks that were written to the target directory, in the
+    /// order in which they were finished.
+    pub written: Vec<WrittenEntry>,
+    pub skipped: Vec<SkippedEntry>,
+    /// The entries that could not be extracted; only populated if
+    /// `ExtractOptions::continue_on_error` is set, since the extraction stops at the first error
+    /// otherwise.
+    pub failed: Vec<FailedEntry>,
+    /// The sum of the bytes written to files.
+    pub bytes_written: u64,
+    /// The path the previous target directory was moved to by an atomic extraction, if it was
+    /// kept (see `ExtractOptions::keep_backup`), or could not be removed.
+    pub backup: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Directory,
+    File,
+    Symlink,
+}
+
+#[derive(Debug, Clone)]
+pub struct WrittenEntry {
+    /// The sanitized name of the entry, as stored in the archive.
+    pub name: PathBuf,
+    /// The path the entry was written to, relative to the target directory. Differs from `name`
+    /// if the entry was relocated (see `ExtractOptions::prefix`), or renamed by the conflict
+    /// strategy.
+    pub path: PathBuf,
+    pub kind: EntryKind,
+    /// The number of bytes written; `0` for directories and symlinks.
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FailedEntry {
+    pub name: PathBuf,
+    /// The message of the error that stopped the extraction of the entry.
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SkippedEntry {
+    pub name: PathBuf,
+    pub reason: SkipReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The entry name is absolute, or contains components that would place the entry outside
+    /// the target directory.
+    UnsafePath,
+
+    /// The entry was rejected by the filter set with `ExtractOptions::filter` or
+    /// `ExtractOptions::entry_filter`, or by the filter passed to
+    /// `ZipArchiveExtensions::extract_matching`.
+    Filtered,
+
+    /// The entry lies outside the subtree selected with `ExtractOptions::prefix`, or its path
+    /// has no more components than are removed by `ExtractOptions::strip_components`.
+    OutsideSubtree,
+
+    /// The entry is a symbolic link, but symlink restoration is not enabled (see
+    /// `ExtractOptions::restore_symlinks`), or not supported on the current platform.
+    Symlink,
+
+    /// The entry is a symbolic link whose resolved target lies outside the extraction root. Such
+    /// links are not created, since they would allow writing to arbitrary locations; a link that
+    /// only escapes the root because of links restored after it is removed again.
+    ///
+    /// `target`: the link target as stored in the archive
+    SymlinkEscapesRoot { target: PathBuf },
+
+    /// The entry's path already exists, and the conflict strategy kept the existing file (see
+    /// `ExtractOptions::conflict_strategy`).
+    Conflict,
+}
+
+impl ExtractionReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn trace_written(
+        &mut self,
+        name: PathBuf,
+        path: PathBuf,
+        kind: EntryKind,
+        bytes: u64,
+    ) {
+        self.bytes_written = self.bytes_written.saturating_add(bytes);
+        self.written.push(WrittenEntry {
+            name,
+            path,
+            kind,
+            bytes,
+        });
+    }
+
+    /// Removes a written entry from the report, such as a link that was removed again.
+    pub(crate) fn retract_written(&mut self, name: &Path) {
+        if let Some(index) = self.written.iter().rposition(|entry| entry.name == name) {
+            let entry = self.written.remove(index);
+            self.bytes_written = self.bytes_written.saturating_sub(entry.bytes);
+        }
+    }
+
+    pub(crate) fn trace_skipped(&mut self, name: PathBuf, reason: SkipReason) {
+        self.skipped.push(SkippedEntry { name, reason });
+    }
+
+    pub(crate) fn trace_failed(&mut self, name: PathBuf, error: &ZipError) {
+        self.failed.push(FailedEntry {
+            name,
+            message: error_message(error),
+        });
+    }
+}
+
+/// Returns the message of an error. The message of an I/O error is taken from the wrapped error,
+/// since `ZipError` displays all of them as "i/o error".
+pub(crate) fn error_message(error: &ZipError) -> String {
+    match error {
+        ZipError::Io(error) => error.to_string(),
+        error => error.to_string(),
+    }
+}
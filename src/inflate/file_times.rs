@@ -0,0 +1,73 @@
+use crate::utilities::date_time_utils::{date_time_to_system_time, unix_seconds_to_system_time};
+use std::fs::{File, FileTimes, OpenOptions};
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::time::SystemTime;
+use zip::ExtraField;
+use zip::read::ZipFile;
+
+/// The modification and access times of an archive entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct EntryTimes {
+    pub(crate) modified: SystemTime,
+    pub(crate) accessed: SystemTime,
+}
+
+impl EntryTimes {
+    /// Reads the times of the given entry. The extended timestamp extra field is preferred, since
+    /// it is stored in UTC with a resolution of one second; the MS-DOS timestamp is used as a
+    /// fallback. If no access time is stored, the modification time is used instead.
+    pub(crate) fn from_entry<R: Read>(entry: &ZipFile<R>) -> Option<Self> {
+        let mut modified: Option<SystemTime> = None;
+        let mut accessed: Option<SystemTime> = None;
+        for field in entry.extra_data_fields() {
+            if let ExtraField::ExtendedTimestamp(timestamp) = field {
+                modified = modified.or(timestamp.mod_time().map(unix_seconds_to_system_time));
+                accessed = accessed.or(timestamp.ac_time().map(unix_seconds_to_system_time));
+            }
+        }
+        let modified = modified.or_else(|| {
+            entry
+                .last_modified()
+                .and_then(|date_time| date_time_to_system_time(&date_time))
+        })?;
+        Some(Self {
+            modified,
+            accessed: accessed.unwrap_or(modified),
+        })
+    }
+
+    /// Applies the times to the file or directory at `path`.
+    pub(crate) fn apply(&self, path: &Path) -> io::Result<()> {
+        let times = FileTimes::new()
+            .set_modified(self.modified)
+            .set_accessed(self.accessed);
+        open_for_times(path)?.set_times(times)
+    }
+}
+
+#[cfg(unix)]
+fn open_for_times(path: &Path) -> io::Result<File> {
+    if path.is_dir() {
+        File::open(path)
+    } else {
+        OpenOptions::new().write(true).open(path)
+    }
+}
+
+#[cfg(windows)]
+fn open_for_times(path: &Path) -> io::Result<File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    // Required to obtain a handle to a directory.
+    const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x02000000;
+    OpenOptions::new()
+        .write(true)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn open_for_times(path: &Path) -> io::Result<File> {
+    OpenOptions::new().write(true).open(path)
+}
@@ -0,0 +1,137 @@
+use std::fs::File;
+use std::io;
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+use zip::result::ZipResult;
+
+/// The outcome of testing a single entry with `ZipArchiveExtensions::test`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryTestResult {
+    pub file_number: usize,
+    /// The entry name as stored in the archive.
+    pub name: PathBuf,
+    pub status: EntryTestStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryTestStatus {
+    /// The entry was decompressed, and its size and CRC32 match the values declared in the
+    /// archive.
+    Ok,
+
+    /// The entry is encrypted; its data was not verified.
+    Encrypted,
+
+    /// The entry decompressed to a different number of bytes than declared.
+    SizeMismatch { declared: u64, actual: u64 },
+
+    /// The CRC32 of the decompressed data differs from the declared checksum.
+    CrcMismatch { declared: u32, actual: u32 },
+
+    /// The entry could not be read or decompressed, such as because of a truncated archive, a
+    /// corrupt compressed stream, or an unsupported compression method.
+    Unreadable { message: String },
+}
+
+impl EntryTestResult {
+    /// Returns `true` if the entry was verified successfully.
+    pub fn is_ok(&self) -> bool {
+        self.status == EntryTestStatus::Ok
+    }
+}
+
+/// Tests the integrity of a ZIP file, similar to `unzip -t`: every entry is decompressed, and its
+/// size and CRC32 are compared against the values declared in the archive. Nothing is written to
+/// disk. An error is returned only if the archive cannot be opened.
+pub fn zip_test(archive_file: &Path) -> ZipResult<Vec<EntryTestResult>> {
+    let file = File::open(archive_file)?;
+    let mut archive = ZipArchive::new(file)?;
+    test_entries(&mut archive)
+}
+
+/// Decompresses every entry of the archive to a sink, and checks its size and CRC32.
+pub(crate) fn test_entries<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> ZipResult<Vec<EntryTestResult>> {
+    let mut results = Vec::with_capacity(archive.len());
+    for file_number in 0..archive.len() {
+        let (name, encrypted) = {
+            let entry = archive.by_index_raw(file_number)?;
+            (PathBuf::from(entry.name()), entry.encrypted())
+        };
+        let status = if encrypted {
+            EntryTestStatus::Encrypted
+        } else {
+            match archive.by_index(file_number) {
+                Ok(mut entry) => {
+                    let mut sink = ChecksumSink::new();
+                    let copied = io::copy(&mut entry, &mut sink);
+                    verify(copied, &sink, entry.size(), entry.crc32())
+                }
+                Err(error) => EntryTestStatus::Unreadable {
+                    message: error.to_string(),
+                },
+            }
+        };
+        results.push(EntryTestResult {
+            file_number,
+            name,
+            status,
+        });
+    }
+    Ok(results)
+}
+
+fn verify(
+    copied: io::Result<u64>,
+    sink: &ChecksumSink,
+    declared_size: u64,
+    declared_crc: u32,
+) -> EntryTestStatus {
+    let actual_crc = sink.hasher.clone().finalize();
+    let crc_mismatch = EntryTestStatus::CrcMismatch {
+        declared: declared_crc,
+        actual: actual_crc,
+    };
+    match copied {
+        // The decompressor reports a checksum error once all data has been read.
+        Err(_) if sink.size == declared_size && actual_crc != declared_crc => crc_mismatch,
+        Err(error) => EntryTestStatus::Unreadable {
+            message: error.to_string(),
+        },
+        Ok(_) if sink.size != declared_size => EntryTestStatus::SizeMismatch {
+            declared: declared_size,
+            actual: sink.size,
+        },
+        Ok(_) if actual_crc != declared_crc => crc_mismatch,
+        Ok(_) => EntryTestStatus::Ok,
+    }
+}
+
+/// A writer that discards its input, and keeps track of its size and CRC32.
+struct ChecksumSink {
+    hasher: crc32fast::Hasher,
+    size: u64,
+}
+
+impl ChecksumSink {
+    fn new() -> Self {
+        Self {
+            hasher: crc32fast::Hasher::new(),
+            size: 0,
+        }
+    }
+}
+
+impl Write for ChecksumSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.hasher.update(buf);
+        self.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
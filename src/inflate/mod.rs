@@ -1,4 +1,19 @@
+pub mod conflict_strategy;
+pub mod extract_filter;
+pub mod extract_limits;
+pub mod extract_observer;
+pub mod extract_options;
+pub mod extract_password;
+mod extraction;
+pub mod extraction_report;
+mod file_times;
+pub mod integrity;
 pub mod is_zip;
+mod parallel;
+mod staging;
+mod subtree;
+mod symlinks;
+pub mod unix_permissions;
 pub mod zip_archive;
 pub mod zip_archive_extensions;
 pub mod zip_extract;
@@ -0,0 +1,241 @@
+use crate::inflate::conflict_strategy::ConflictStrategy;
+use crate::inflate::extract_options::ExtractOptions;
+use crate::inflate::extraction::{Extraction, PlannedEntry};
+use crate::inflate::extraction_report::{EntryKind, ExtractionReport, SkipReason};
+use crate::inflate::file_times::EntryTimes;
+use crate::inflate::zip_archive::total_uncompressed;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io;
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::SystemTime;
+use zip::ZipArchive;
+use zip::result::{ZipError, ZipResult};
+
+/// A planned entry, and its index in the archive.
+struct EntryJob {
+    file_number: usize,
+    planned: PlannedEntry,
+}
+
+/// Extracts the archive at `archive_file` to the existing directory `root`, using up to
+/// `threads` workers that each read the archive through their own file handle.
+///
+/// The extraction runs in three phases. First, all entries are planned in archive order: skipped
+/// entries are recorded, directories and the parents of all files are created, and entries that
+/// map to the same path or to existing files are resolved. Then, the workers decompress disjoint
+/// sets of file entries. Finally, symlinks are restored in archive order, once all files exist,
+/// together with the entries that are extracted sequentially (see `plan`).
+pub(crate) fn extract_parallel(
+    archive_file: &Path,
+    root: &Path,
+    options: &ExtractOptions,
+    threads: usize,
+) -> ZipResult<ExtractionReport> {
+    let mut archive = ZipArchive::new(File::open(archive_file)?)?;
+    let extraction = Extraction::new(root.canonicalize()?, options);
+    extraction.check_entry_count(archive.len())?;
+    if extraction.is_observed() {
+        let total_uncompressed = total_uncompressed(&mut archive)?;
+        extraction.start(archive.len(), total_uncompressed);
+    }
+
+    let (jobs, sequential) =
+        plan(&mut archive, &extraction, options).map_err(|error| extraction.abort(error))?;
+
+    extract_files(archive_file, &extraction, &jobs, threads)
+        .map_err(|error| extraction.abort(error))?;
+
+    for job in sequential {
+        let name = job.planned.sanitized_name.clone();
+        extraction
+            .open_entry(&mut archive, job.file_number)
+            .and_then(|mut entry| extraction.extract_planned(&mut entry, job.planned))
+            .or_else(|error| extraction.fail_entry(name, error))
+            .map_err(|error| extraction.abort(error))?;
+    }
+    extraction.finish()
+}
+
+/// Plans all entries in archive order, creates directories, and returns the file jobs and the
+/// entries to extract sequentially afterward: the symlinks to restore, and all entries from the
+/// first one below a restored symlink on. Such an entry is written through the link, so neither
+/// it nor its parents can be created before the link exists, and later entries may be written
+/// through the link as well.
+///
+/// If several file entries map to the same path, the conflict strategy picks one of them before any
+/// file is written: the last one for `ConflictStrategy::Overwrite`, which matches the result of a
+/// sequential extraction, the first one for `ConflictStrategy::Skip`, and the newest one for
+/// `ConflictStrategy::OverwriteIfNewer`. The other strategies fail the extraction, since their
+/// outcome would depend on the order in which the workers finish.
+///
+/// Conflicts with files that existed before the extraction are resolved afterward, in archive
+/// order as well, so that the conflict strategy, including the callback of
+/// `ConflictStrategy::Ask`, is never invoked by several workers at once. An entry renamed by
+/// `ConflictStrategy::Rename` avoids the paths of all other entries.
+fn plan(
+    archive: &mut ZipArchive<File>,
+    extraction: &Extraction,
+    options: &ExtractOptions,
+) -> ZipResult<(Vec<EntryJob>, Vec<EntryJob>)> {
+    let mut jobs: Vec<Option<EntryJob>> = Vec::new();
+    let mut job_by_path: HashMap<PathBuf, (usize, Option<SystemTime>)> = HashMap::new();
+    let mut sequential = Vec::new();
+    let mut link_paths: Vec<PathBuf> = Vec::new();
+    let mut below_link = false;
+    for file_number in 0..archive.len() {
+        let mut entry = archive.by_index_raw(file_number)?;
+        let planned = match extraction.plan_entry(&entry)? {
+            Some(planned) => planned,
+            None => continue,
+        };
+        below_link = below_link
+            || link_paths
+                .iter()
+                .any(|link_path| planned.output_path.starts_with(link_path));
+        if below_link {
+            sequential.push(EntryJob {
+                file_number,
+                planned,
+            });
+            continue;
+        }
+        match planned.kind {
+            EntryKind::Directory => {
+                let name = planned.sanitized_name.clone();
+                extraction
+                    .extract_planned(&mut entry, planned)
+                    .or_else(|error| extraction.fail_entry(name, error))?;
+            }
+            EntryKind::File => {
+                if let Err(error) = extraction.create_parent(&planned) {
+                    extraction.fail_entry(planned.sanitized_name, error)?;
+                    continue;
+                }
+                let modified = EntryTimes::from_entry(&entry).map(|times| times.modified);
+                if let Some(&(previous, previous_modified)) = job_by_path.get(&planned.output_path)
+                {
+                    let replace = match options.conflict_strategy {
+                        ConflictStrategy::Overwrite => true,
+                        ConflictStrategy::Skip => false,
+                        ConflictStrategy::OverwriteIfNewer => {
+                            matches!((previous_modified, modified), (Some(p), Some(m)) if m > p)
+                        }
+                        _ => {
+                            return Err(ZipError::Io(io::Error::new(
+                                ErrorKind::AlreadyExists,
+                                "The archive contains several entries with the same path.",
+                            )));
+                        }
+                    };
+                    if !replace {
+                        extraction.skip(planned.sanitized_name, SkipReason::Conflict);
+                        continue;
+                    }
+                    jobs[previous] = None;
+                }
+                job_by_path.insert(planned.output_path.clone(), (jobs.len(), modified));
+                jobs.push(Some(EntryJob {
+                    file_number,
+                    planned,
+                }));
+            }
+            EntryKind::Symlink => {
+                if options.restore_symlinks {
+                    link_paths.push(planned.output_path.clone());
+                }
+                sequential.push(EntryJob {
+                    file_number,
+                    planned,
+                });
+            }
+        }
+    }
+
+    let jobs: Vec<EntryJob> = jobs.into_iter().flatten().collect();
+    let mut reserved: HashSet<PathBuf> = jobs
+        .iter()
+        .map(|job| job.planned.output_path.clone())
+        .collect();
+    let mut resolved_jobs = Vec::with_capacity(jobs.len());
+    for mut job in jobs {
+        let entry = archive.by_index_raw(job.file_number)?;
+        match extraction.resolve_planned_conflict(&entry, &mut job.planned, &mut reserved) {
+            Ok(true) => resolved_jobs.push(job),
+            Ok(false) => {}
+            Err(error) => extraction.fail_entry(job.planned.sanitized_name, error)?,
+        }
+    }
+    Ok((resolved_jobs, sequential))
+}
+
+/// Distributes the file jobs over the workers. If a job fails, and the error is not recorded in
+/// the report (see `ExtractOptions::continue_on_error`), the remaining jobs are cancelled, and the
+/// error of the failed job with the lowest entry index is returned.
+fn extract_files(
+    archive_file: &Path,
+    extraction: &Extraction,
+    jobs: &[EntryJob],
+    threads: usize,
+) -> ZipResult<()> {
+    let threads = match NonZeroUsize::new(threads) {
+        Some(threads) => threads.get(),
+        None => std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+    }
+    .min(jobs.len());
+
+    let next_job = AtomicUsize::new(0);
+    let cancelled = AtomicBool::new(false);
+    let failure: Mutex<Option<(usize, ZipError)>> = Mutex::new(None);
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                if let Err((file_number, error)) =
+                    run_worker(archive_file, extraction, jobs, &next_job, &cancelled)
+                {
+                    cancelled.store(true, Ordering::SeqCst);
+                    let mut failure = failure.lock().unwrap();
+                    if failure
+                        .as_ref()
+                        .is_none_or(|(first, _)| file_number < *first)
+                    {
+                        *failure = Some((file_number, error));
+                    }
+                }
+            });
+        }
+    });
+    match failure.into_inner().unwrap() {
+        Some((_, error)) => Err(error),
+        None => Ok(()),
+    }
+}
+
+fn run_worker(
+    archive_file: &Path,
+    extraction: &Extraction,
+    jobs: &[EntryJob],
+    next_job: &AtomicUsize,
+    cancelled: &AtomicBool,
+) -> Result<(), (usize, ZipError)> {
+    let mut archive = File::open(archive_file)
+        .map_err(ZipError::from)
+        .and_then(ZipArchive::new)
+        .map_err(|error| (0, error))?;
+    while !cancelled.load(Ordering::SeqCst) {
+        let job = match jobs.get(next_job.fetch_add(1, Ordering::SeqCst)) {
+            Some(job) => job,
+            None => break,
+        };
+        extraction
+            .open_entry(&mut archive, job.file_number)
+            .and_then(|mut entry| extraction.extract_planned(&mut entry, job.planned.clone()))
+            .or_else(|error| extraction.fail_entry(job.planned.sanitized_name.clone(), error))
+            .map_err(|error| (job.file_number, error))?;
+    }
+    Ok(())
+}
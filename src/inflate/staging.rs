@@ -0,0 +1,213 @@
+use std::fs::{self, File};
+use std::io;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// A directory next to the target directory that an atomic extraction writes to. The staging
+/// directory is removed when it is dropped without having been committed.
+pub(crate) struct StagingDirectory {
+    path: PathBuf,
+    target: PathBuf,
+    committed: bool,
+}
+
+impl StagingDirectory {
+    /// Creates an empty staging directory in the parent directory of `target`. The target
+    /// directory does not need to exist, but its parent does.
+    pub(crate) fn create(target: &Path) -> io::Result<Self> {
+        if target.exists() && !target.is_dir() {
+            return Err(invalid_target());
+        }
+        let name = target.file_name().ok_or_else(invalid_target)?;
+        let parent = match target.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.canonicalize()?,
+            _ => Path::new(".").canonicalize()?,
+        };
+        let target = parent.join(name);
+
+        let prefix = format!(".{}.staging-{}", name.to_string_lossy(), std::process::id());
+        let path = create_unique_dir(&parent, &prefix)?;
+        Ok(Self {
+            path,
+            target,
+            committed: false,
+        })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Flushes the staged tree to disk and moves it to the target path. An existing target
+    /// directory is moved to a backup path, which is returned if `keep_backup` is set, and removed
+    /// otherwise. If the replaced directory cannot be removed, its path is returned as well, since
+    /// the new tree is already in place.
+    ///
+    /// On Linux, the staged tree and an existing target directory are exchanged in a single step,
+    /// so the target path always refers to either the old or the new tree. On other platforms, and
+    /// on file systems that do not support exchanging paths, the existing target directory is
+    /// renamed to the backup path right before the staged tree is renamed to the target path, so
+    /// the target path does not exist for a short moment.
+    pub(crate) fn commit(mut self, keep_backup: bool) -> io::Result<Option<PathBuf>> {
+        sync_tree(&self.path)?;
+
+        let backup = if self.target.exists() {
+            let parent = self.target.parent().unwrap_or(Path::new("."));
+            let name = self.target.file_name().unwrap_or_default();
+            let prefix = format!(".{}.backup", name.to_string_lossy());
+            let backup = unique_path(parent, &prefix);
+            Some(replace_directory(&self.path, &self.target, &backup)?)
+        } else {
+            fs::rename(&self.path, &self.target)?;
+            None
+        };
+        self.committed = true;
+        if let Some(parent) = self.target.parent() {
+            sync_directory(parent)?;
+        }
+
+        match backup {
+            Some(backup) if !keep_backup => match fs::remove_dir_all(&backup) {
+                Ok(()) => Ok(None),
+                Err(_) => Ok(Some(backup)),
+            },
+            backup => Ok(backup),
+        }
+    }
+}
+
+impl Drop for StagingDirectory {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+}
+
+/// Replaces the directory at `target` by the directory at `staging`, and returns the path the
+/// replaced directory was moved to; that is `backup`, unless the exchanged directory could not be
+/// renamed, in which case it is left at the staging path.
+#[cfg(target_os = "linux")]
+fn replace_directory(staging: &Path, target: &Path, backup: &Path) -> io::Result<PathBuf> {
+    match exchange_paths(staging, target) {
+        Ok(()) => match fs::rename(staging, backup) {
+            Ok(()) => Ok(backup.to_path_buf()),
+            Err(_) => Ok(staging.to_path_buf()),
+        },
+        Err(error) if matches!(error.raw_os_error(), Some(libc::EINVAL | libc::ENOSYS)) => {
+            rename_into_place(staging, target, backup)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn replace_directory(staging: &Path, target: &Path, backup: &Path) -> io::Result<PathBuf> {
+    rename_into_place(staging, target, backup)
+}
+
+/// Atomically exchanges the files at two paths.
+#[cfg(target_os = "linux")]
+fn exchange_paths(a: &Path, b: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    let a = CString::new(a.as_os_str().as_bytes())?;
+    let b = CString::new(b.as_os_str().as_bytes())?;
+    // SAFETY: both paths are valid, nul-terminated strings that outlive the call.
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_renameat2,
+            libc::AT_FDCWD,
+            a.as_ptr(),
+            libc::AT_FDCWD,
+            b.as_ptr(),
+            libc::RENAME_EXCHANGE,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Renames the directory at `target` to `backup`, and the directory at `staging` to `target`. The
+/// target path does not exist in between; if the second rename fails, the first one is undone.
+fn rename_into_place(staging: &Path, target: &Path, backup: &Path) -> io::Result<PathBuf> {
+    fs::rename(target, backup)?;
+    if let Err(error) = fs::rename(staging, target) {
+        let _ = fs::rename(backup, target);
+        return Err(error);
+    }
+    Ok(backup.to_path_buf())
+}
+
+fn invalid_target() -> io::Error {
+    io::Error::new(
+        ErrorKind::InvalidInput,
+        "The specified path does not indicate a valid directory path.",
+    )
+}
+
+fn create_unique_dir(parent: &Path, prefix: &str) -> io::Result<PathBuf> {
+    let mut counter = 0;
+    loop {
+        let path = parent.join(format!("{}-{}", prefix, counter));
+        match fs::create_dir(&path) {
+            Ok(()) => return Ok(path),
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => counter += 1,
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn unique_path(parent: &Path, prefix: &str) -> PathBuf {
+    let mut counter = 0;
+    loop {
+        let path = parent.join(format!("{}-{}", prefix, counter));
+        if fs::symlink_metadata(&path).is_err() {
+            return path;
+        }
+        counter += 1;
+    }
+}
+
+/// Flushes all files and directories below `root` to disk. Symbolic links are not followed.
+fn sync_tree(root: &Path) -> io::Result<()> {
+    let mut directories = vec![root.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        for entry in fs::read_dir(&directory)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                directories.push(entry.path());
+            } else if file_type.is_file() {
+                sync_file(&entry.path())?;
+            }
+        }
+        sync_directory(&directory)?;
+    }
+    Ok(())
+}
+
+/// Flushes a file to disk. Files that cannot be opened for reading, because the permissions
+/// restored from the archive do not allow it, are left to the operating system.
+fn sync_file(path: &Path) -> io::Result<()> {
+    match File::open(path) {
+        Ok(file) => file.sync_all(),
+        Err(error) if error.kind() == ErrorKind::PermissionDenied => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(unix)]
+fn sync_directory(path: &Path) -> io::Result<()> {
+    sync_file(path)
+}
+
+#[cfg(not(unix))]
+fn sync_directory(_path: &Path) -> io::Result<()> {
+    // Directory handles cannot be flushed without platform-specific flags; renames are made
+    // durable by the file system journal.
+    Ok(())
+}
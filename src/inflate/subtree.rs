@@ -0,0 +1,39 @@
+use std::ffi::OsStr;
+use std::path::{Component, Path, PathBuf};
+
+/// Returns the path, relative to the target directory, that a sanitized entry path is extracted
+/// to, or `None` if the entry lies outside `prefix`, or has no more than `strip_components`
+/// components.
+pub(crate) fn relocate(
+    sanitized_name: &Path,
+    prefix: Option<&Path>,
+    strip_components: usize,
+) -> Option<PathBuf> {
+    let components = normal_components(sanitized_name);
+    if let Some(prefix) = prefix
+        && !components.starts_with(&normal_components(prefix))
+    {
+        return None;
+    }
+    if strip_components > 0 && components.len() <= strip_components {
+        return None;
+    }
+    Some(components[strip_components..].iter().collect())
+}
+
+/// Resolves `.` and `..` lexically. Sanitized entry paths never climb above their first
+/// component, but may contain `..` (such as `a/b/../c`), which would otherwise escape the target
+/// directory once leading components are stripped.
+fn normal_components(path: &Path) -> Vec<&OsStr> {
+    let mut components = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => components.push(name),
+            Component::ParentDir => {
+                components.pop();
+            }
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+    components
+}
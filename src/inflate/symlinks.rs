@@ -0,0 +1,140 @@
+use std::io;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+/// Upper bound for the size of a symlink entry; link targets are paths, so anything larger is
+/// either malformed or malicious.
+const MAX_SYMLINK_TARGET_SIZE: u64 = 4096;
+
+/// Reads the link target stored as the content of a symlink entry.
+pub(crate) fn read_symlink_target<R: Read>(entry: &mut R) -> io::Result<PathBuf> {
+    let mut target = Vec::new();
+    entry
+        .take(MAX_SYMLINK_TARGET_SIZE + 1)
+        .read_to_end(&mut target)?;
+    if target.len() as u64 > MAX_SYMLINK_TARGET_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "The symlink target exceeds the maximum supported length.",
+        ));
+    }
+    Ok(bytes_to_path(target))
+}
+
+/// Creates a symbolic link at `link_path`, if its `target` resolves to a location inside `root`,
+/// and replaces an existing file or link at that path. Returns `false` without touching the file
+/// system if the target escapes the root.
+pub(crate) fn restore_symlink(root: &Path, link_path: &Path, target: &Path) -> io::Result<bool> {
+    if let Some(parent) = link_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let resolved = match resolve_within_root(root, link_path, target)? {
+        Some(resolved) => resolved,
+        None => return Ok(false),
+    };
+    if let Ok(existing) = std::fs::symlink_metadata(link_path) {
+        if existing.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "The specified file already exists.",
+            ));
+        }
+        std::fs::remove_file(link_path)?;
+    }
+    create_symlink(target, &resolved, link_path)?;
+    Ok(true)
+}
+
+/// Resolves the target of a link located at `link_path` and returns it, if the resolved location
+/// stays inside `root`. Both `root` and the link's parent directory must exist. The target is
+/// resolved against the file system, so that links created earlier in the same extraction are
+/// taken into account.
+pub(crate) fn resolve_within_root(
+    root: &Path,
+    link_path: &Path,
+    target: &Path,
+) -> io::Result<Option<PathBuf>> {
+    let parent = match link_path.parent() {
+        Some(parent) => parent.canonicalize()?,
+        None => return Ok(None),
+    };
+    if !parent.starts_with(root) {
+        return Ok(None);
+    }
+    if target
+        .components()
+        .any(|component| matches!(component, Component::RootDir | Component::Prefix(_)))
+    {
+        return Ok(None);
+    }
+    Ok(resolve_path(&parent.join(target))?.filter(|resolved| resolved.starts_with(root)))
+}
+
+/// Resolves `path` against the file system: the longest existing prefix of the path is
+/// canonicalized, which follows all links in it, and the remaining components are applied to it.
+///
+/// Returns `None` if the path cannot be resolved reliably, because it passes a dangling link, or
+/// goes up (`..`) from a location that does not exist yet, and thus may be created as a link later.
+pub(crate) fn resolve_path(path: &Path) -> io::Result<Option<PathBuf>> {
+    let existing = match path
+        .ancestors()
+        .find(|ancestor| std::fs::symlink_metadata(ancestor).is_ok())
+    {
+        Some(existing) => existing,
+        None => return Ok(None),
+    };
+    let mut resolved = match existing.canonicalize() {
+        Ok(resolved) => resolved,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    let remaining = path.strip_prefix(existing).unwrap_or(Path::new(""));
+    for component in remaining.components() {
+        match component {
+            Component::Normal(name) => resolved.push(name),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return Ok(None),
+        }
+    }
+    Ok(Some(resolved))
+}
+
+/// Creates a symbolic link at `link_path` that points to `target`.
+#[cfg(unix)]
+fn create_symlink(target: &Path, _resolved: &Path, link_path: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link_path)
+}
+
+/// Creates a symbolic link at `link_path` that points to `target`.
+#[cfg(windows)]
+fn create_symlink(target: &Path, resolved: &Path, link_path: &Path) -> io::Result<()> {
+    if resolved.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link_path)
+    } else {
+        std::os::windows::fs::symlink_file(target, link_path)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn create_symlink(_target: &Path, _resolved: &Path, _link_path: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Symbolic links are not supported on this platform.",
+    ))
+}
+
+/// Returns whether symbolic links can be created on the current platform.
+pub(crate) const fn symlinks_supported() -> bool {
+    cfg!(any(unix, windows))
+}
+
+#[cfg(unix)]
+fn bytes_to_path(bytes: Vec<u8>) -> PathBuf {
+    use std::os::unix::ffi::OsStringExt;
+    PathBuf::from(std::ffi::OsString::from_vec(bytes))
+}
+
+#[cfg(not(unix))]
+fn bytes_to_path(bytes: Vec<u8>) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&bytes).replace('/', "\\"))
+}
@@ -0,0 +1,21 @@
+use std::io;
+use std::path::Path;
+
+/// The default mask applied to restored permissions; keeps the `rwx` bits for user, group and
+/// others, but strips the setuid, setgid and sticky bits.
+pub const DEFAULT_PERMISSIONS_MASK: u32 = 0o777;
+
+/// Applies the permission bits of an entry's Unix mode to the file or directory at `path`. The
+/// file type bits of `mode` are ignored, and the remaining bits are restricted by `mask`.
+#[cfg(unix)]
+pub(crate) fn apply_unix_mode(path: &Path, mode: u32, mask: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let permissions = std::fs::Permissions::from_mode(mode & 0o7777 & mask);
+    std::fs::set_permissions(path, permissions)
+}
+
+/// Permission bits are only restored on Unix platforms; elsewhere this is a no-op.
+#[cfg(not(unix))]
+pub(crate) fn apply_unix_mode(_path: &Path, _mode: u32, _mask: u32) -> io::Result<()> {
+    Ok(())
+}
@@ -1,39 +1,49 @@
-use crate::file_utils::file_write_all_bytes;
+use crate::file_utils::file_write_from_reader;
+use crate::inflate::extract_filter::ExtractFilter;
+use crate::inflate::extract_options::ExtractOptions;
+use crate::inflate::extract_password::open_entry;
+use crate::inflate::extraction::Extraction;
+use crate::inflate::extraction_report::ExtractionReport;
+use crate::inflate::integrity::{EntryTestResult, test_entries};
+use crate::inflate::staging::StagingDirectory;
 use crate::inflate::zip_archive_extensions::ZipArchiveExtensions;
 use std::io;
 use std::io::{Error, ErrorKind, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use zip::ZipArchive;
 use zip::read::ZipFile;
 use zip::result::{ZipError, ZipResult};
 
 impl<R: Read + io::Seek> ZipArchiveExtensions for ZipArchive<R> {
-    fn extract(&mut self, target_directory: &PathBuf) -> ZipResult<()> {
-        if !target_directory.is_dir() {
-            return Err(ZipError::Io(Error::new(
-                ErrorKind::InvalidInput,
-                "The specified path does not indicate a valid directory path.",
-            )));
-        }
+    fn extract(&mut self, target_directory: &PathBuf) -> ZipResult<ExtractionReport> {
+        self.extract_with_options(target_directory, &ExtractOptions::default())
+    }
 
-        for file_number in 0..self.len() {
-            let mut next: ZipFile<R> = self.by_index(file_number)?;
-            let sanitized_name = match next.enclosed_name() {
-                Some(name) => name,
-                None => continue,
-            };
-            if next.is_dir() {
-                let extracted_folder_path = target_directory.join(sanitized_name);
-                std::fs::create_dir_all(extracted_folder_path)?;
-            } else if next.is_file() {
-                let mut buffer: Vec<u8> = Vec::new();
-                let _bytes_read = next.read_to_end(&mut buffer)?;
-                let extracted_file_path = target_directory.join(sanitized_name);
-                file_write_all_bytes(extracted_file_path, buffer.as_ref(), true)?;
-            }
-        }
+    fn extract_with_options(
+        &mut self,
+        target_directory: &Path,
+        options: &ExtractOptions,
+    ) -> ZipResult<ExtractionReport> {
+        extract_to_target(target_directory, options, |root| {
+            extract_entries(self, root, options)
+        })
+    }
 
-        Ok(())
+    fn extract_with_password(
+        &mut self,
+        target_directory: &Path,
+        password: &[u8],
+    ) -> ZipResult<ExtractionReport> {
+        let options = ExtractOptions::default().password(password);
+        self.extract_with_options(target_directory, &options)
+    }
+
+    fn extract_matching(
+        &mut self,
+        target_directory: &Path,
+        filter: ExtractFilter,
+    ) -> ZipResult<ExtractionReport> {
+        self.extract_with_options(target_directory, &ExtractOptions::default().filter(filter))
     }
 
     fn extract_file(
@@ -42,14 +52,15 @@ impl<R: Read + io::Seek> ZipArchiveExtensions for ZipArchive<R> {
         destination_file_path: &PathBuf,
         overwrite: bool,
     ) -> ZipResult<()> {
-        let mut buffer: Vec<u8> = Vec::new();
-        self.extract_file_to_memory(file_number, &mut buffer)?;
-        file_write_all_bytes(
-            destination_file_path.to_path_buf(),
-            buffer.as_ref(),
-            overwrite,
-        )?;
-        Ok(())
+        let mut next: ZipFile<R> = self.by_index(file_number)?;
+        if next.is_file() {
+            file_write_from_reader(destination_file_path.to_path_buf(), &mut next, overwrite)?;
+            return Ok(());
+        }
+        Err(ZipError::Io(Error::new(
+            ErrorKind::InvalidInput,
+            "The specified index does not indicate a file entry.",
+        )))
     }
 
     fn extract_file_to_memory(
@@ -68,14 +79,36 @@ impl<R: Read + io::Seek> ZipArchiveExtensions for ZipArchive<R> {
         )))
     }
 
+    fn extract_file_to_memory_with_password(
+        &mut self,
+        file_number: usize,
+        buffer: &mut Vec<u8>,
+        password: &[u8],
+    ) -> ZipResult<()> {
+        let mut next: ZipFile<R> = open_entry(self, file_number, |_| Some(password.to_vec()))?;
+        if next.is_file() {
+            let _bytes_read = next.read_to_end(buffer)?;
+            return Ok(());
+        }
+        Err(ZipError::Io(Error::new(
+            ErrorKind::InvalidInput,
+            "The specified index does not indicate a file entry.",
+        )))
+    }
+
+    fn test(&mut self) -> ZipResult<Vec<EntryTestResult>> {
+        test_entries(self)
+    }
+
     fn entry_path(&mut self, file_number: usize) -> ZipResult<PathBuf> {
-        let next: ZipFile<R> = self.by_index(file_number)?;
+        let next: ZipFile<R> = self.by_index_raw(file_number)?;
         Ok(next.mangled_name())
     }
 
     fn file_number(&mut self, entry_path: &PathBuf) -> Option<usize> {
         for file_number in 0..self.len() {
-            if let Ok(next) = self.by_index(file_number) {
+            // Only the metadata is read, so encrypted entries are found as well.
+            if let Ok(next) = self.by_index_raw(file_number) {
                 let sanitized_name = next.mangled_name();
                 if sanitized_name == *entry_path {
                     return Some(file_number);
@@ -85,3 +118,77 @@ impl<R: Read + io::Seek> ZipArchiveExtensions for ZipArchive<R> {
         None
     }
 }
+
+/// Extracts all entries of the archive to the existing directory `root`.
+fn extract_entries<R: Read + io::Seek>(
+    archive: &mut ZipArchive<R>,
+    root: &Path,
+    options: &ExtractOptions,
+) -> ZipResult<ExtractionReport> {
+    let extraction = Extraction::new(root.canonicalize()?, options);
+    extraction.check_entry_count(archive.len())?;
+    if extraction.is_observed() {
+        let total_uncompressed = total_uncompressed(archive)?;
+        extraction.start(archive.len(), total_uncompressed);
+    }
+    for file_number in 0..archive.len() {
+        // Entries are planned from their metadata, so that skipped entries are never decrypted.
+        let planned = match archive
+            .by_index_raw(file_number)
+            .and_then(|entry| extraction.plan_entry(&entry))
+            .map_err(|error| extraction.abort(error))?
+        {
+            Some(planned) => planned,
+            None => continue,
+        };
+        let name = planned.sanitized_name.clone();
+        extraction
+            .open_entry(archive, file_number)
+            .and_then(|mut next| extraction.extract_planned(&mut next, planned))
+            .or_else(|error| extraction.fail_entry(name, error))
+            .map_err(|error| extraction.abort(error))?;
+    }
+    extraction.finish()
+}
+
+/// Checks the target directory, and runs `extract` either on the target directory itself, or on a
+/// staging directory that replaces the target directory afterward (see `ExtractOptions::atomic`).
+pub(crate) fn extract_to_target<F>(
+    target_directory: &Path,
+    options: &ExtractOptions,
+    extract: F,
+) -> ZipResult<ExtractionReport>
+where
+    F: FnOnce(&Path) -> ZipResult<ExtractionReport>,
+{
+    if options.atomic {
+        let staging = StagingDirectory::create(target_directory)?;
+        let mut report = extract(staging.path())?;
+        report.backup = staging.commit(options.keep_backup)?;
+        return Ok(report);
+    }
+
+    if !target_directory.is_dir() {
+        return Err(ZipError::Io(Error::new(
+            ErrorKind::InvalidInput,
+            "The specified path does not indicate a valid directory path.",
+        )));
+    }
+    extract(target_directory)
+}
+
+/// Returns the sum of the uncompressed sizes declared by the entries of the archive.
+pub(crate) fn total_uncompressed<R: Read + io::Seek>(
+    archive: &mut ZipArchive<R>,
+) -> ZipResult<u64> {
+    if let Some(size) = archive.decompressed_size() {
+        return Ok(u64::try_from(size).unwrap_or(u64::MAX));
+    }
+    // Entries with data descriptors are not covered by `decompressed_size`; the central directory
+    // still declares their sizes.
+    let mut total: u64 = 0;
+    for file_number in 0..archive.len() {
+        total = total.saturating_add(archive.by_index_raw(file_number)?.size());
+    }
+    Ok(total)
+}
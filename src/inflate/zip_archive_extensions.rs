@@ -1,9 +1,39 @@
-use std::path::PathBuf;
+use crate::inflate::extract_filter::ExtractFilter;
+use crate::inflate::extract_options::ExtractOptions;
+use crate::inflate::extraction_report::ExtractionReport;
+use crate::inflate::integrity::EntryTestResult;
+use std::path::{Path, PathBuf};
 use zip::result::ZipResult;
 
 pub trait ZipArchiveExtensions {
-    /// Extracts the current archive to the given directory path.
-    fn extract(&mut self, path: &PathBuf) -> ZipResult<()>;
+    /// Extracts the current archive to the given directory path. Returns a report that lists the
+    /// entries that were written and the entries that were not extracted.
+    fn extract(&mut self, path: &PathBuf) -> ZipResult<ExtractionReport>;
+
+    /// Extracts the current archive to the given directory path, using the specified options.
+    /// Returns a report that lists the entries that were written and the entries that were not
+    /// extracted.
+    fn extract_with_options(
+        &mut self,
+        path: &Path,
+        options: &ExtractOptions,
+    ) -> ZipResult<ExtractionReport>;
+
+    /// Extracts the current archive to the given directory path, decrypting encrypted entries with
+    /// the given password. Fails with a `PasswordError` if an entry cannot be decrypted.
+    fn extract_with_password(
+        &mut self,
+        path: &Path,
+        password: &[u8],
+    ) -> ZipResult<ExtractionReport>;
+
+    /// Extracts the entries of the current archive that match the filter to the given directory
+    /// path. Returns a report that lists the entries that were not extracted.
+    fn extract_matching(
+        &mut self,
+        path: &Path,
+        filter: ExtractFilter,
+    ) -> ZipResult<ExtractionReport>;
 
     /// Extracts an entry in the zip archive to a file.
     fn extract_file(
@@ -17,6 +47,22 @@ pub trait ZipArchiveExtensions {
     fn extract_file_to_memory(&mut self, file_number: usize, buffer: &mut Vec<u8>)
     -> ZipResult<()>;
 
+    /// Extracts an entry in the ZIP archive to the given memory buffer, decrypting it with the
+    /// given password if it is encrypted. Fails with a `PasswordError` if the entry cannot be
+    /// decrypted.
+    fn extract_file_to_memory_with_password(
+        &mut self,
+        file_number: usize,
+        buffer: &mut Vec<u8>,
+        password: &[u8],
+    ) -> ZipResult<()>;
+
+    /// Tests the integrity of the current archive, similar to `unzip -t`: every entry is
+    /// decompressed, and its size and CRC32 are compared against the values declared in the
+    /// archive. Nothing is written to disk. Returns the result of each entry; encrypted entries
+    /// are not verified.
+    fn test(&mut self) -> ZipResult<Vec<EntryTestResult>>;
+
     /// Gets an entry´s path.
     fn entry_path(&mut self, file_number: usize) -> ZipResult<PathBuf>;
 
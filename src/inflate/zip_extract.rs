@@ -1,14 +1,74 @@
+use crate::inflate::extract_filter::ExtractFilter;
+use crate::inflate::extract_options::ExtractOptions;
+use crate::inflate::extraction_report::ExtractionReport;
+use crate::inflate::parallel;
+use crate::inflate::zip_archive::extract_to_target;
 use crate::inflate::zip_archive_extensions::ZipArchiveExtensions;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use zip::ZipArchive;
 use zip::result::{ZipError, ZipResult};
 
 /// Extracts a ZIP file to the given directory.
-pub fn zip_extract(archive_file: &PathBuf, target_dir: &PathBuf) -> ZipResult<()> {
+pub fn zip_extract(archive_file: &PathBuf, target_dir: &PathBuf) -> ZipResult<ExtractionReport> {
     let file = File::open(archive_file)?;
     let mut archive = ZipArchive::new(file)?;
-    archive.extract(target_dir)
+    // `ZipArchive::extract` of the zip crate takes precedence over the extension method.
+    ZipArchiveExtensions::extract(&mut archive, target_dir)
+}
+
+/// Extracts a ZIP file to the given directory, using the specified options.
+pub fn zip_extract_with_options(
+    archive_file: &Path,
+    target_dir: &Path,
+    options: &ExtractOptions,
+) -> ZipResult<ExtractionReport> {
+    let file = File::open(archive_file)?;
+    let mut archive = ZipArchive::new(file)?;
+    archive.extract_with_options(target_dir, options)
+}
+
+/// Extracts a ZIP file to the given directory, decrypting encrypted entries with the given
+/// password.
+pub fn zip_extract_with_password(
+    archive_file: &Path,
+    target_dir: &Path,
+    password: &[u8],
+) -> ZipResult<ExtractionReport> {
+    let file = File::open(archive_file)?;
+    let mut archive = ZipArchive::new(file)?;
+    archive.extract_with_password(target_dir, password)
+}
+
+/// Extracts a ZIP file to the given directory, using the specified options and up to `threads`
+/// worker threads; `0` uses one thread per available CPU. Each worker reads the archive through
+/// its own file handle and decompresses a disjoint set of entries.
+///
+/// Directories are created, and entries that map to the same path or to existing files are
+/// resolved, in archive order before any file is written, so the result does not depend on the
+/// order in which the workers finish. Symlinks are restored after all files have been extracted;
+/// once an entry lies below a restored symlink, it and all later entries are extracted in archive
+/// order after the symlinks, as a sequential extraction would.
+pub fn zip_extract_parallel(
+    archive_file: &Path,
+    target_dir: &Path,
+    options: &ExtractOptions,
+    threads: usize,
+) -> ZipResult<ExtractionReport> {
+    extract_to_target(target_dir, options, |root| {
+        parallel::extract_parallel(archive_file, root, options, threads)
+    })
+}
+
+/// Extracts the entries of a ZIP file that match the filter to the given directory.
+pub fn zip_extract_matching(
+    archive_file: &Path,
+    target_dir: &Path,
+    filter: ExtractFilter,
+) -> ZipResult<ExtractionReport> {
+    let file = File::open(archive_file)?;
+    let mut archive = ZipArchive::new(file)?;
+    archive.extract_matching(target_dir, filter)
 }
 
 /// Extracts and entry in the ZIP archive to the given directory.
@@ -42,3 +102,20 @@ pub fn zip_extract_file_to_memory(
     };
     archive.extract_file_to_memory(file_number, buffer)
 }
+
+/// Extracts an entry in the ZIP archive to the given memory buffer, decrypting it with the given
+/// password if it is encrypted.
+pub fn zip_extract_file_to_memory_with_password(
+    archive_file: &Path,
+    entry_path: &Path,
+    buffer: &mut Vec<u8>,
+    password: &[u8],
+) -> ZipResult<()> {
+    let file = File::open(archive_file)?;
+    let mut archive = ZipArchive::new(file)?;
+    let file_number: usize = match archive.file_number(&entry_path.to_path_buf()) {
+        Some(index) => index,
+        None => return Err(ZipError::FileNotFound),
+    };
+    archive.extract_file_to_memory_with_password(file_number, buffer, password)
+}
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use crate::audit::audited_extraction::{AuditedExtractionError, zip_extract_audited};
+    use crate::audit::policy::{AuditPolicy, PolicyViolation};
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io;
+    use std::io::Write;
+    use zip::result::ZipError;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract_audited_extracts_archive_that_satisfies_policy() {
+        // Arrange
+        let archive = create_archive(false);
+
+        // Act
+        let result = zip_extract_audited(
+            &archive.archive_path,
+            &archive.target_path,
+            &AuditPolicy::new(),
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(result.audit.entry_count, 2);
+        assert!(result.extraction.is_ok());
+        assert!(archive.target_path.join("data/file.txt").exists());
+    }
+
+    #[test]
+    fn extract_audited_refuses_archive_that_violates_policy() {
+        // Arrange
+        let archive = create_archive(true);
+
+        // Act
+        let result = zip_extract_audited(
+            &archive.archive_path,
+            &archive.target_path,
+            &AuditPolicy::new(),
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(result.audit.symlinks_point_outside_root, 1);
+        match result.extraction {
+            Err(AuditedExtractionError::PolicyViolated(violations)) => {
+                assert_eq!(violations, vec![PolicyViolation::SymlinksOutsideRoot(1)]);
+            }
+            other => panic!("unexpected extraction result: {:?}", other),
+        }
+        assert_eq!(fs::read_dir(&archive.target_path).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn extract_audited_extracts_archive_if_policy_allows_findings() {
+        // Arrange
+        let archive = create_archive(true);
+        let policy = AuditPolicy {
+            allow_symlinks_outside_root: true,
+            ..AuditPolicy::new()
+        };
+
+        // Act
+        let result =
+            zip_extract_audited(&archive.archive_path, &archive.target_path, &policy).unwrap();
+
+        // Assert
+        let extraction = result.extraction.unwrap();
+        assert_eq!(extraction.skipped.len(), 1);
+        assert!(archive.target_path.join("data/file.txt").exists());
+    }
+
+    #[test]
+    fn extract_audited_refuses_archive_with_parent_components() {
+        // Arrange
+        let options = SimpleFileOptions::default();
+        let archive = TestArchive::create(|zip_writer| {
+            zip_writer
+                .start_file("data/../../escape.txt", options)
+                .unwrap();
+            zip_writer.write_all(b"escape").unwrap();
+        });
+
+        // Act
+        let result = zip_extract_audited(
+            &archive.archive_path,
+            &archive.target_path,
+            &AuditPolicy::new(),
+        )
+        .unwrap();
+
+        // Assert
+        assert!(result.audit.has_parent_components);
+        match result.extraction {
+            Err(AuditedExtractionError::PolicyViolated(violations)) => {
+                assert_eq!(violations, vec![PolicyViolation::ParentComponents]);
+            }
+            other => panic!("unexpected extraction result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn extract_audited_does_not_treat_empty_entries_as_compression_bombs() {
+        // Arrange
+        let options = SimpleFileOptions::default();
+        let archive = TestArchive::create(|zip_writer| {
+            zip_writer.add_directory("data", options).unwrap();
+            zip_writer.start_file("data/empty.txt", options).unwrap();
+        });
+
+        // Act
+        let result = zip_extract_audited(
+            &archive.archive_path,
+            &archive.target_path,
+            &AuditPolicy::new(),
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(result.audit.max_ratio, 1.0);
+        assert!(result.extraction.is_ok());
+    }
+
+    #[test]
+    fn extraction_error_displays_message_of_io_error() {
+        // Arrange
+        let error = AuditedExtractionError::Extraction(ZipError::Io(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Invalid checksum",
+        )));
+
+        // Act
+        let message = error.to_string();
+
+        // Assert
+        assert_eq!(message, "The extraction failed: Invalid checksum");
+    }
+
+    fn create_archive(with_escaping_symlink: bool) -> TestArchive {
+        let options = SimpleFileOptions::default();
+        TestArchive::create(|zip_writer| {
+            zip_writer.add_directory("data", options).unwrap();
+            zip_writer.start_file("data/file.txt", options).unwrap();
+            zip_writer.write_all(b"content").unwrap();
+            if with_escaping_symlink {
+                zip_writer
+                    .add_symlink("data/link", "../../outside", options)
+                    .unwrap();
+            }
+        })
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use crate::audit::report::SuspiciousReason;
+    use crate::audit::zip_audit_reader::{zip_audit, zip_audit_verified};
+    use std::io::{Cursor, Write};
+    use std::path::PathBuf;
+    use zip::CompressionMethod;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    const CONTENT: &[u8] = b"content that is checked";
+
+    #[test]
+    fn audit_verified_flags_entries_with_mismatching_data() {
+        // Arrange
+        let mut content = create_archive();
+        let offset = content
+            .windows(CONTENT.len())
+            .position(|window| window == CONTENT)
+            .unwrap();
+        content[offset] ^= 0xFF;
+
+        // Act
+        let metadata_report = zip_audit(Cursor::new(content.clone())).unwrap();
+        let verified_report = zip_audit_verified(Cursor::new(content)).unwrap();
+
+        // Assert
+        assert!(!metadata_report.truncated_or_mismatch);
+        assert!(verified_report.truncated_or_mismatch);
+        assert_eq!(verified_report.suspicious_entries.len(), 1);
+        assert_eq!(
+            verified_report.suspicious_entries[0].name,
+            PathBuf::from("file.txt")
+        );
+        assert!(matches!(
+            verified_report.suspicious_entries[0].reason,
+            SuspiciousReason::HeaderMismatch
+        ));
+    }
+
+    #[test]
+    fn audit_verified_accepts_intact_archive() {
+        // Arrange
+        let content = create_archive();
+
+        // Act
+        let report = zip_audit_verified(Cursor::new(content)).unwrap();
+
+        // Assert
+        assert!(!report.truncated_or_mismatch);
+        assert!(report.suspicious_entries.is_empty());
+    }
+
+    fn create_archive() -> Vec<u8> {
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let mut zip_writer = ZipWriter::new(Cursor::new(Vec::new()));
+        zip_writer.start_file("file.txt", options).unwrap();
+        zip_writer.write_all(CONTENT).unwrap();
+        zip_writer.finish().unwrap().into_inner()
+    }
+}
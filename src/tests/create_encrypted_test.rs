@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use crate::deflate::aes_encryption::{
+        zip_create_from_directory_encrypted, zip_create_from_directory_encrypted_with_handler,
+    };
+    use crate::deflate::zip_ignore_entry_handler::ZipIgnoreEntryHandler;
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::extract_password::PasswordError;
+    use crate::inflate::zip_extract::{zip_extract_with_options, zip_extract_with_password};
+    use crate::tests::test_source::TestSource;
+    use std::fs::{self, File};
+    use zip::{AesMode, ZipArchive};
+
+    #[test]
+    fn create_encrypted_encrypts_every_file_entry() {
+        // Arrange
+        let source = create_source_directory();
+
+        // Act
+        zip_create_from_directory_encrypted(
+            &source.archive_path,
+            &source.source_path,
+            "secret",
+            AesMode::Aes256,
+        )
+        .unwrap();
+
+        // Assert
+        let mut archive = ZipArchive::new(File::open(&source.archive_path).unwrap()).unwrap();
+        assert_eq!(archive.len(), 4);
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i).unwrap();
+            assert_eq!(entry.encrypted(), entry.is_file(), "{}", entry.name());
+        }
+        zip_extract_with_password(&source.archive_path, &source.target_path, b"secret").unwrap();
+        let content = fs::read_to_string(source.target_path.join("docs/readme.txt")).unwrap();
+        assert_eq!(content, "readme");
+    }
+
+    #[test]
+    fn create_encrypted_requires_password_to_extract() {
+        // Arrange
+        let source = create_source_directory();
+        zip_create_from_directory_encrypted(
+            &source.archive_path,
+            &source.source_path,
+            "secret",
+            AesMode::Aes128,
+        )
+        .unwrap();
+
+        // Act
+        let error = zip_extract_with_options(
+            &source.archive_path,
+            &source.target_path,
+            &ExtractOptions::default(),
+        )
+        .unwrap_err();
+
+        // Assert
+        assert!(matches!(
+            PasswordError::from_error(&error),
+            Some(PasswordError::PasswordRequired { .. })
+        ));
+    }
+
+    #[test]
+    fn create_encrypted_composes_with_zip_ignore_handler() {
+        // Arrange
+        let source = create_source_directory();
+        fs::write(source.source_path.join(".zipignore"), "*.log\n").unwrap();
+
+        // Act
+        zip_create_from_directory_encrypted_with_handler(
+            &source.archive_path,
+            &source.source_path,
+            "secret",
+            AesMode::Aes256,
+            &ZipIgnoreEntryHandler::new(),
+        )
+        .unwrap();
+
+        // Assert
+        let mut archive = ZipArchive::new(File::open(&source.archive_path).unwrap()).unwrap();
+        let names: Vec<String> = archive.file_names().map(String::from).collect();
+        assert!(!names.contains(&"app.log".to_string()));
+        assert!(names.contains(&"notes.txt".to_string()));
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i).unwrap();
+            assert_eq!(entry.encrypted(), entry.is_file(), "{}", entry.name());
+        }
+    }
+
+    fn create_source_directory() -> TestSource {
+        let source = TestSource::new();
+        source.write("notes.txt", "notes");
+        source.write("app.log", "log");
+        source.write("docs/readme.txt", "readme");
+        fs::create_dir_all(&source.target_path).unwrap();
+        source
+    }
+}
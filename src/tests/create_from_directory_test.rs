@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use crate::deflate::zip_writer::zip_create_from_directory_with_options;
+    use std::fs::{self, File};
+    use std::io::Read;
+    use tempfile::tempdir;
+    use zip::CompressionMethod;
+    use zip::ZipArchive;
+    use zip::write::SimpleFileOptions;
+
+    const LARGE_FILE_SIZE: usize = 5 * 1024 * 1024;
+
+    #[test]
+    fn create_from_directory_streams_large_files() {
+        // Arrange
+        let tmp = tempdir().unwrap();
+        let source_path = tmp.path().join("source");
+        fs::create_dir_all(source_path.join("sub")).unwrap();
+        let expected: Vec<u8> = (0..LARGE_FILE_SIZE).map(|i| (i % 251) as u8).collect();
+        fs::write(source_path.join("sub/large.bin"), &expected).unwrap();
+        fs::write(source_path.join("small.txt"), "small").unwrap();
+        let archive_path = tmp.path().join("archive.zip");
+
+        // Act
+        zip_create_from_directory_with_options(&archive_path, &source_path, |_| {
+            SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .large_file(true)
+        })
+        .unwrap();
+
+        // Assert
+        let mut archive = ZipArchive::new(File::open(&archive_path).unwrap()).unwrap();
+        let mut actual = Vec::new();
+        archive
+            .by_name("sub/large.bin")
+            .unwrap()
+            .read_to_end(&mut actual)
+            .unwrap();
+        assert_eq!(actual, expected);
+        let mut small = String::new();
+        archive
+            .by_name("small.txt")
+            .unwrap()
+            .read_to_string(&mut small)
+            .unwrap();
+        assert_eq!(small, "small");
+    }
+}
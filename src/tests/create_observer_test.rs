@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use crate::deflate::create_observer::{CreateObserver, ObservingEntryHandler};
+    use crate::deflate::zip_ignore_entry_handler::ZipIgnoreEntryHandler;
+    use crate::deflate::zip_writer::zip_create_from_directory_observed;
+    use crate::deflate::zip_writer_extensions::ZipWriterExtensions;
+    use crate::tests::test_source::TestSource;
+    use std::collections::BTreeMap;
+    use std::fs::File;
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+    use zip::result::ZipError;
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    struct RecordingObserver {
+        root: PathBuf,
+        events: Mutex<BTreeMap<String, Vec<String>>>,
+    }
+
+    impl RecordingObserver {
+        fn new(root: &Path) -> Self {
+            Self {
+                root: root.to_path_buf(),
+                events: Mutex::default(),
+            }
+        }
+
+        fn record(&self, entry_path: &Path, event: String) {
+            let name = entry_path.strip_prefix(&self.root).unwrap();
+            self.record_name(&name.to_string_lossy(), event);
+        }
+
+        fn record_name(&self, name: &str, event: String) {
+            let mut events = self.events.lock().unwrap();
+            events.entry(name.to_string()).or_default().push(event);
+        }
+
+        fn events_of(&self, name: &str) -> Vec<String> {
+            self.events.lock().unwrap()[name].clone()
+        }
+    }
+
+    impl CreateObserver for RecordingObserver {
+        fn entry_visited(&self, entry_path: &Path) {
+            self.record(entry_path, "visited".to_string());
+        }
+
+        fn entry_added(&self, entry_path: &Path, bytes_read: u64) {
+            self.record(entry_path, format!("added {bytes_read}"));
+        }
+
+        fn entry_ignored(&self, entry_path: &Path) {
+            self.record(entry_path, "ignored".to_string());
+        }
+
+        fn entry_failed(&self, entry_path: &Path, _error: &ZipError) {
+            self.record(entry_path, "failed".to_string());
+        }
+
+        fn entry_compressed(&self, name: &str, uncompressed_size: u64, compressed_size: u64) {
+            let event = format!("compressed {uncompressed_size} {compressed_size}");
+            self.record_name(name, event);
+        }
+    }
+
+    #[test]
+    fn create_reports_added_and_ignored_entries() {
+        // Arrange
+        let source = create_source_directory();
+        let observer = RecordingObserver::new(&source.source_path);
+        let handler = ObservingEntryHandler::new(ZipIgnoreEntryHandler::new(), &observer);
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+
+        // Act
+        zip_writer
+            .create_from_directory_with_options(
+                &source.source_path,
+                |_| SimpleFileOptions::default(),
+                &handler,
+            )
+            .unwrap();
+
+        // Assert
+        assert_eq!(observer.events_of("a.txt"), vec!["visited", "added 11"]);
+        assert_eq!(observer.events_of("app.log"), vec!["visited", "ignored"]);
+        assert_eq!(observer.events_of("sub"), vec!["visited", "added 0"]);
+    }
+
+    #[test]
+    fn create_observed_reports_compressed_sizes() {
+        // Arrange
+        let source = create_source_directory();
+        let observer = RecordingObserver::new(&source.source_path);
+
+        // Act
+        zip_create_from_directory_observed(
+            &source.archive_path,
+            &source.source_path,
+            |_| SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+            &ZipIgnoreEntryHandler::new(),
+            &observer,
+        )
+        .unwrap();
+
+        // Assert
+        let zeros = observer.events_of("sub/zeros.bin");
+        assert_eq!(zeros[..2], ["visited", "added 4096"]);
+        let compressed: Vec<u64> = zeros[2]
+            .split(' ')
+            .skip(1)
+            .map(|n| n.parse().unwrap())
+            .collect();
+        assert_eq!(compressed[0], 4096);
+        assert!(compressed[1] < 4096);
+        assert_eq!(observer.events_of("app.log"), vec!["visited", "ignored"]);
+    }
+
+    fn create_source_directory() -> TestSource {
+        let source = TestSource::new();
+        source.write("a.txt", "Hello World");
+        source.write("app.log", "ignored log");
+        source.write(".zipignore", "*.log\n");
+        source.write("sub/zeros.bin", vec![0u8; 4096]);
+        source
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_limits::ExtractLimits;
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::zip_extract::zip_extract_with_options;
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::Write;
+    use std::path::Path;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract_atomic_creates_target_directory() {
+        // Arrange
+        let archive = create_archive();
+        let options = ExtractOptions::default().atomic(true);
+
+        // Act
+        let report =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)
+                .unwrap();
+
+        // Assert
+        assert!(archive.target_path.join("data/file.txt").exists());
+        assert_eq!(report.backup, None);
+        assert_eq!(
+            directory_names(archive.target_path.parent().unwrap()),
+            vec!["archive.zip", "target"]
+        );
+    }
+
+    #[test]
+    fn extract_atomic_replaces_existing_directory_and_keeps_backup() {
+        // Arrange
+        let archive = create_archive();
+        fs::create_dir_all(&archive.target_path).unwrap();
+        fs::write(archive.target_path.join("old.txt"), "old").unwrap();
+        let options = ExtractOptions::default().atomic(true).keep_backup(true);
+
+        // Act
+        let report =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)
+                .unwrap();
+
+        // Assert
+        assert!(archive.target_path.join("data/file.txt").exists());
+        assert!(!archive.target_path.join("old.txt").exists());
+        let backup = report.backup.unwrap();
+        assert_eq!(fs::read_to_string(backup.join("old.txt")).unwrap(), "old");
+    }
+
+    #[test]
+    fn extract_atomic_removes_replaced_directory_without_backup() {
+        // Arrange
+        let archive = create_archive();
+        fs::create_dir_all(&archive.target_path).unwrap();
+        fs::write(archive.target_path.join("old.txt"), "old").unwrap();
+        let options = ExtractOptions::default().atomic(true);
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        assert!(!archive.target_path.join("old.txt").exists());
+        assert_eq!(
+            directory_names(archive.target_path.parent().unwrap()),
+            vec!["archive.zip", "target"]
+        );
+    }
+
+    #[test]
+    fn extract_atomic_leaves_target_untouched_if_extraction_fails() {
+        // Arrange
+        let archive = create_archive();
+        fs::create_dir_all(&archive.target_path).unwrap();
+        fs::write(archive.target_path.join("old.txt"), "old").unwrap();
+        let options = ExtractOptions::default()
+            .atomic(true)
+            .limits(ExtractLimits::default().max_entry_size(4));
+
+        // Act
+        let result =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options);
+
+        // Assert
+        assert!(result.is_err());
+        assert_eq!(directory_names(&archive.target_path), vec!["old.txt"]);
+        assert_eq!(
+            directory_names(archive.target_path.parent().unwrap()),
+            vec!["archive.zip", "target"]
+        );
+    }
+
+    fn directory_names(path: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(path)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn create_archive() -> TestArchive {
+        let options = SimpleFileOptions::default();
+        TestArchive::without_target(|zip_writer| {
+            zip_writer.add_directory("data", options).unwrap();
+            zip_writer.start_file("data/file.txt", options).unwrap();
+            zip_writer.write_all(b"content").unwrap();
+        })
+    }
+}
@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::conflict_strategy::{ConflictResolution, ConflictStrategy};
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::extraction_report::SkipReason;
+    use crate::inflate::zip_extract::{zip_extract_parallel, zip_extract_with_options};
+    use crate::tests::test_archive::TestArchive;
+    use std::fs::{self, File};
+    use std::io::{ErrorKind, Write};
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, SystemTime};
+    use zip::DateTime;
+    use zip::result::ZipError;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract_with_skip_keeps_existing_files() {
+        // Arrange
+        let archive = create_archive(&[("a.txt", "from archive"), ("b.txt", "b")]);
+        fs::write(archive.target_path.join("a.txt"), "existing").unwrap();
+        let options = ExtractOptions::default().conflict_strategy(ConflictStrategy::Skip);
+
+        // Act
+        let report =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)
+                .unwrap();
+
+        // Assert
+        assert_eq!(read(&archive.target_path, "a.txt"), "existing");
+        assert_eq!(read(&archive.target_path, "b.txt"), "b");
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].name, PathBuf::from("a.txt"));
+        assert_eq!(report.skipped[0].reason, SkipReason::Conflict);
+    }
+
+    #[test]
+    fn extract_with_overwrite_if_newer_compares_modification_times() {
+        // Arrange
+        let archive = create_archive(&[("old.txt", "from archive"), ("new.txt", "from archive")]);
+        let old_path = archive.target_path.join("old.txt");
+        fs::write(&old_path, "existing").unwrap();
+        set_modified(
+            &old_path,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(946_684_800),
+        );
+        fs::write(archive.target_path.join("new.txt"), "existing").unwrap();
+        let options =
+            ExtractOptions::default().conflict_strategy(ConflictStrategy::OverwriteIfNewer);
+
+        // Act
+        let report =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)
+                .unwrap();
+
+        // Assert
+        assert_eq!(read(&archive.target_path, "old.txt"), "from archive");
+        assert_eq!(read(&archive.target_path, "new.txt"), "existing");
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].name, PathBuf::from("new.txt"));
+    }
+
+    #[test]
+    fn extract_with_rename_writes_next_free_name() {
+        // Arrange
+        let archive = create_archive(&[("a.txt", "from archive"), ("README", "readme")]);
+        fs::write(archive.target_path.join("a.txt"), "existing").unwrap();
+        fs::write(archive.target_path.join("a (1).txt"), "existing").unwrap();
+        fs::write(archive.target_path.join("README"), "existing").unwrap();
+        let options = ExtractOptions::default().conflict_strategy(ConflictStrategy::Rename);
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        assert_eq!(read(&archive.target_path, "a.txt"), "existing");
+        assert_eq!(read(&archive.target_path, "a (1).txt"), "existing");
+        assert_eq!(read(&archive.target_path, "a (2).txt"), "from archive");
+        assert_eq!(read(&archive.target_path, "README (1)"), "readme");
+    }
+
+    #[test]
+    fn extract_with_fail_returns_already_exists() {
+        // Arrange
+        let archive = create_archive(&[("a.txt", "from archive")]);
+        fs::write(archive.target_path.join("a.txt"), "existing").unwrap();
+        let options = ExtractOptions::default().conflict_strategy(ConflictStrategy::Fail);
+
+        // Act
+        let error = zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)
+            .unwrap_err();
+
+        // Assert
+        assert!(matches!(error, ZipError::Io(ref e) if e.kind() == ErrorKind::AlreadyExists));
+        assert_eq!(read(&archive.target_path, "a.txt"), "existing");
+    }
+
+    #[test]
+    fn extract_with_callback_asks_per_conflict() {
+        // Arrange
+        let archive = create_archive(&[("a.txt", "a"), ("b.txt", "b"), ("c.txt", "c")]);
+        fs::write(archive.target_path.join("a.txt"), "existing").unwrap();
+        fs::write(archive.target_path.join("b.txt"), "existing").unwrap();
+        let asked = Arc::new(Mutex::new(Vec::new()));
+        let asked_by_callback = asked.clone();
+        let options =
+            ExtractOptions::default().conflict_strategy(ConflictStrategy::ask(move |conflict| {
+                asked_by_callback
+                    .lock()
+                    .unwrap()
+                    .push(conflict.name.to_path_buf());
+                if conflict.name == Path::new("a.txt") {
+                    ConflictResolution::Overwrite
+                } else {
+                    ConflictResolution::Skip
+                }
+            }));
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        assert_eq!(
+            *asked.lock().unwrap(),
+            vec![PathBuf::from("a.txt"), PathBuf::from("b.txt")]
+        );
+        assert_eq!(read(&archive.target_path, "a.txt"), "a");
+        assert_eq!(read(&archive.target_path, "b.txt"), "existing");
+        assert_eq!(read(&archive.target_path, "c.txt"), "c");
+    }
+
+    #[test]
+    fn extract_parallel_with_skip_keeps_first_of_duplicate_entries() {
+        // Arrange
+        let archive = create_archive(&[("a.txt", "first"), ("x/../a.txt", "second")]);
+        let options = ExtractOptions::default().conflict_strategy(ConflictStrategy::Skip);
+
+        // Act
+        let report =
+            zip_extract_parallel(&archive.archive_path, &archive.target_path, &options, 2).unwrap();
+
+        // Assert
+        assert_eq!(read(&archive.target_path, "a.txt"), "first");
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].reason, SkipReason::Conflict);
+    }
+
+    #[test]
+    fn extract_parallel_with_rename_resolves_existing_files_in_archive_order() {
+        for _ in 0..20 {
+            // Arrange
+            let archive = create_archive(&[("a.txt", "a"), ("a (1).txt", "a (1)")]);
+            fs::write(archive.target_path.join("a.txt"), "existing").unwrap();
+            let options = ExtractOptions::default().conflict_strategy(ConflictStrategy::Rename);
+
+            // Act
+            zip_extract_parallel(&archive.archive_path, &archive.target_path, &options, 2).unwrap();
+
+            // Assert
+            assert_eq!(read(&archive.target_path, "a.txt"), "existing");
+            assert_eq!(read(&archive.target_path, "a (1).txt"), "a (1)");
+            assert_eq!(read(&archive.target_path, "a (2).txt"), "a");
+        }
+    }
+
+    #[test]
+    fn extract_parallel_with_callback_asks_in_archive_order() {
+        // Arrange
+        let archive = create_archive(&[("a.txt", "a"), ("b.txt", "b"), ("c.txt", "c")]);
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(archive.target_path.join(name), "existing").unwrap();
+        }
+        let asked = Arc::new(Mutex::new(Vec::new()));
+        let asked_by_callback = asked.clone();
+        let options =
+            ExtractOptions::default().conflict_strategy(ConflictStrategy::ask(move |conflict| {
+                asked_by_callback
+                    .lock()
+                    .unwrap()
+                    .push((conflict.name.to_path_buf(), std::thread::current().id()));
+                ConflictResolution::Overwrite
+            }));
+
+        // Act
+        zip_extract_parallel(&archive.archive_path, &archive.target_path, &options, 3).unwrap();
+
+        // Assert
+        let asked = asked.lock().unwrap();
+        let names: Vec<&PathBuf> = asked.iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            vec![
+                &PathBuf::from("a.txt"),
+                &PathBuf::from("b.txt"),
+                &PathBuf::from("c.txt")
+            ]
+        );
+        assert!(
+            asked
+                .iter()
+                .all(|(_, thread)| *thread == std::thread::current().id())
+        );
+        assert_eq!(read(&archive.target_path, "c.txt"), "c");
+    }
+
+    fn read(directory: &Path, name: &str) -> String {
+        fs::read_to_string(directory.join(name)).unwrap()
+    }
+
+    fn set_modified(path: &Path, modified: SystemTime) {
+        let file = File::options().write(true).open(path).unwrap();
+        file.set_modified(modified).unwrap();
+    }
+
+    fn create_archive(entries: &[(&str, &str)]) -> TestArchive {
+        let modified = DateTime::from_date_and_time(2020, 1, 1, 0, 0, 0).unwrap();
+        let options = SimpleFileOptions::default().last_modified_time(modified);
+
+        TestArchive::create(|zip_writer| {
+            for (name, content) in entries {
+                zip_writer.start_file(*name, options).unwrap();
+                zip_writer.write_all(content.as_bytes()).unwrap();
+            }
+        })
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_filter::ExtractFilter;
+    use crate::inflate::extraction_report::SkipReason;
+    use crate::inflate::zip_extract::zip_extract_matching;
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract_matching_extracts_entries_matching_include_patterns() {
+        // Arrange
+        let archive = create_archive();
+        let filter = ExtractFilter::new().include(&["assets/**/*.png"]).unwrap();
+
+        // Act
+        let report =
+            zip_extract_matching(&archive.archive_path, &archive.target_path, filter).unwrap();
+
+        // Assert
+        assert_eq!(
+            extracted_files(&archive),
+            vec!["assets/icons/a.png", "assets/logo.png"]
+        );
+        assert!(
+            report
+                .skipped
+                .iter()
+                .all(|s| s.reason == SkipReason::Filtered)
+        );
+    }
+
+    #[test]
+    fn extract_matching_leaves_out_entries_matching_exclude_patterns() {
+        // Arrange
+        let archive = create_archive();
+        let filter = ExtractFilter::new()
+            .exclude(&["*.png", "!logo.png", "docs/"])
+            .unwrap();
+
+        // Act
+        zip_extract_matching(&archive.archive_path, &archive.target_path, filter).unwrap();
+
+        // Assert
+        assert_eq!(
+            extracted_files(&archive),
+            vec!["README.md", "assets/logo.png"]
+        );
+    }
+
+    #[test]
+    fn extract_matching_applies_rules_file() {
+        // Arrange
+        let archive = create_archive();
+        let rules_file = archive.target_path.with_file_name(".zipignore");
+        fs::write(&rules_file, "# documentation\ndocs\n*.md\n").unwrap();
+        let filter = ExtractFilter::new().exclude_from_file(&rules_file).unwrap();
+
+        // Act
+        zip_extract_matching(&archive.archive_path, &archive.target_path, filter).unwrap();
+
+        // Assert
+        assert_eq!(
+            extracted_files(&archive),
+            vec!["assets/icons/a.png", "assets/logo.png"]
+        );
+    }
+
+    #[test]
+    fn extract_matching_applies_predicate_over_entry_metadata() {
+        // Arrange
+        let archive = create_archive();
+        let filter = ExtractFilter::new()
+            .include(&["assets/"])
+            .unwrap()
+            .predicate(|entry| entry.is_dir || entry.size > 4);
+
+        // Act
+        zip_extract_matching(&archive.archive_path, &archive.target_path, filter).unwrap();
+
+        // Assert
+        assert_eq!(extracted_files(&archive), vec!["assets/logo.png"]);
+    }
+
+    #[test]
+    fn extract_filter_rejects_invalid_patterns() {
+        // Act
+        let result = ExtractFilter::new().include(&["assets/{a,b"]);
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    fn extracted_files(archive: &TestArchive) -> Vec<String> {
+        let mut files = Vec::new();
+        let mut directories = vec![archive.target_path.clone()];
+        while let Some(directory) = directories.pop() {
+            for entry in fs::read_dir(directory).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    directories.push(path);
+                } else {
+                    let relative = path.strip_prefix(&archive.target_path).unwrap();
+                    files.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    fn create_archive() -> TestArchive {
+        let options = SimpleFileOptions::default();
+        TestArchive::create(|zip_writer| {
+            for (name, content) in [
+                ("README.md", "readme"),
+                ("assets/logo.png", "large logo"),
+                ("assets/icons/a.png", "a"),
+                ("docs/guide.md", "guide"),
+                ("docs/image.png", "image"),
+            ] {
+                zip_writer.start_file(name, options).unwrap();
+                zip_writer.write_all(content.as_bytes()).unwrap();
+            }
+        })
+    }
+}
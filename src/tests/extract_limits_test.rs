@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_limits::{ExtractLimitError, ExtractLimits};
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::zip_extract::zip_extract_with_options;
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use zip::CompressionMethod;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract_fails_with_typed_error_if_entry_is_too_large() {
+        // Arrange
+        let archive = create_archive();
+        let limits = ExtractLimits::default().max_entry_size(1024);
+
+        // Act
+        let error = extract(&archive, limits).unwrap_err();
+
+        // Assert
+        assert_eq!(
+            ExtractLimitError::from_error(&error),
+            Some(&ExtractLimitError::EntrySizeExceeded {
+                name: PathBuf::from("data/zeros.bin"),
+                limit: 1024
+            })
+        );
+    }
+
+    #[test]
+    fn extract_fails_with_typed_error_if_compression_ratio_is_too_high() {
+        // Arrange
+        let archive = create_archive();
+        let limits = ExtractLimits::default().max_compression_ratio(100.0);
+
+        // Act
+        let error = extract(&archive, limits).unwrap_err();
+
+        // Assert
+        assert!(matches!(
+            ExtractLimitError::from_error(&error),
+            Some(ExtractLimitError::CompressionRatioExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn extract_fails_with_typed_error_if_path_is_too_deep() {
+        // Arrange
+        let archive = create_archive();
+        let limits = ExtractLimits::default().max_path_depth(2);
+
+        // Act
+        let error = extract(&archive, limits).unwrap_err();
+
+        // Assert
+        assert_eq!(
+            ExtractLimitError::from_error(&error),
+            Some(&ExtractLimitError::PathTooDeep {
+                name: PathBuf::from("data/nested/deep.txt"),
+                limit: 2,
+                depth: 3
+            })
+        );
+    }
+
+    #[test]
+    fn extract_removes_partial_output_if_limit_is_exceeded() {
+        // Arrange
+        let archive = create_archive();
+        fs::write(archive.target_path.join("existing.txt"), "keep").unwrap();
+        let limits = ExtractLimits::default().max_total_size(64 * 1024);
+
+        // Act
+        let error = extract(&archive, limits).unwrap_err();
+
+        // Assert
+        assert_eq!(
+            ExtractLimitError::from_error(&error),
+            Some(&ExtractLimitError::TotalSizeExceeded { limit: 64 * 1024 })
+        );
+        let remaining: Vec<PathBuf> = fs::read_dir(&archive.target_path)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into())
+            .collect();
+        assert_eq!(remaining, vec![PathBuf::from("existing.txt")]);
+    }
+
+    #[test]
+    fn extract_keeps_existing_file_if_entry_exceeds_limit() {
+        // Arrange
+        let archive = create_archive();
+        fs::write(archive.target_path.join("a.txt"), "original").unwrap();
+        let limits = ExtractLimits::default().max_entry_size(10);
+
+        // Act
+        let error = extract(&archive, limits).unwrap_err();
+
+        // Assert
+        assert!(matches!(
+            ExtractLimitError::from_error(&error),
+            Some(ExtractLimitError::EntrySizeExceeded { .. })
+        ));
+        assert_eq!(
+            fs::read_to_string(archive.target_path.join("a.txt")).unwrap(),
+            "original"
+        );
+        assert_eq!(fs::read_dir(&archive.target_path).unwrap().count(), 1);
+    }
+
+    fn extract(archive: &TestArchive, limits: ExtractLimits) -> zip::result::ZipResult<()> {
+        let options = ExtractOptions::default().limits(limits);
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)?;
+        Ok(())
+    }
+
+    fn create_archive() -> TestArchive {
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        TestArchive::create(|zip_writer| {
+            zip_writer.start_file("a.txt", options).unwrap();
+            zip_writer.write_all(b"Hello World").unwrap();
+            zip_writer
+                .start_file("data/nested/deep.txt", options)
+                .unwrap();
+            zip_writer.write_all(b"deep").unwrap();
+            zip_writer.start_file("data/zeros.bin", options).unwrap();
+            zip_writer.write_all(&vec![0u8; 1024 * 1024]).unwrap();
+        })
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_observer::{ExtractObserver, ProgressExtractObserver};
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::extraction_report::{ExtractionReport, SkipReason};
+    use crate::inflate::zip_extract::zip_extract_with_options;
+    use crate::tests::test_archive::TestArchive;
+    use std::io::Write;
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+    use zip::write::SimpleFileOptions;
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: Mutex<Vec<String>>,
+        bytes_written: Mutex<u64>,
+    }
+
+    impl RecordingObserver {
+        fn record(&self, event: String) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    impl ExtractObserver for RecordingObserver {
+        fn archive_started(&self, entry_count: usize, total_uncompressed: u64) {
+            self.record(format!(
+                "archive_started {entry_count} {total_uncompressed}"
+            ));
+        }
+
+        fn entry_started(&self, name: &Path, uncompressed_size: u64) {
+            self.record(format!(
+                "entry_started {} {uncompressed_size}",
+                name.display()
+            ));
+        }
+
+        fn bytes_written(&self, _name: &Path, bytes: u64) {
+            *self.bytes_written.lock().unwrap() += bytes;
+        }
+
+        fn entry_finished(&self, name: &Path, bytes_written: u64) {
+            self.record(format!("entry_finished {} {bytes_written}", name.display()));
+        }
+
+        fn entry_skipped(&self, name: &Path, reason: &SkipReason) {
+            self.record(format!("entry_skipped {} {:?}", name.display(), reason));
+        }
+
+        fn archive_finished(&self, report: &ExtractionReport) {
+            self.record(format!("archive_finished {}", report.skipped.len()));
+        }
+    }
+
+    #[test]
+    fn extract_notifies_observer_about_each_entry() {
+        // Arrange
+        let archive = create_archive();
+        let observer = Arc::new(RecordingObserver::default());
+        let options = ExtractOptions::default()
+            .entry_filter(|path| path != Path::new("data/ignored.txt"))
+            .observer(observer.clone());
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        assert_eq!(
+            *observer.events.lock().unwrap(),
+            vec![
+                "archive_started 4 1038",
+                "entry_started data/ 0",
+                "entry_finished data/ 0",
+                "entry_started data/file.txt 7",
+                "entry_finished data/file.txt 7",
+                "entry_started data/zeros.bin 1024",
+                "entry_finished data/zeros.bin 1024",
+                "entry_skipped data/ignored.txt Filtered",
+                "archive_finished 1",
+            ]
+        );
+        assert_eq!(*observer.bytes_written.lock().unwrap(), 1031);
+    }
+
+    #[test]
+    fn extract_reports_increasing_percentages_until_complete() {
+        // Arrange
+        let archive = create_archive();
+        let percentages = Arc::new(Mutex::new(Vec::new()));
+        let recorded = percentages.clone();
+        let options =
+            ExtractOptions::default().observer(ProgressExtractObserver::new(move |percent| {
+                recorded.lock().unwrap().push(percent)
+            }));
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        let percentages = percentages.lock().unwrap();
+        assert!(percentages.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(percentages.last(), Some(&100));
+    }
+
+    fn create_archive() -> TestArchive {
+        let options = SimpleFileOptions::default();
+        TestArchive::create(|zip_writer| {
+            zip_writer.add_directory("data", options).unwrap();
+            zip_writer.start_file("data/file.txt", options).unwrap();
+            zip_writer.write_all(b"content").unwrap();
+            zip_writer.start_file("data/zeros.bin", options).unwrap();
+            zip_writer.write_all(&[0u8; 1024]).unwrap();
+            zip_writer.start_file("data/ignored.txt", options).unwrap();
+            zip_writer.write_all(b"ignored").unwrap();
+        })
+    }
+}
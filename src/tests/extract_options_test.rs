@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_limits::ExtractLimits;
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::zip_extract::zip_extract_with_options;
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::Write;
+    use std::path::Path;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract_with_options_applies_entry_filter() {
+        // Arrange
+        let archive = create_archive(&[("a.txt", "a"), ("sub/b.log", "b")]);
+        let options = ExtractOptions::default()
+            .entry_filter(|p: &Path| p.extension().is_some_and(|e| e == "txt"));
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        assert!(archive.target_path.join("a.txt").exists());
+        assert!(!archive.target_path.join("sub/b.log").exists());
+    }
+
+    #[test]
+    fn extract_with_options_does_not_overwrite_existing_files() {
+        // Arrange
+        let archive = create_archive(&[("a.txt", "from archive")]);
+        fs::write(archive.target_path.join("a.txt"), "existing").unwrap();
+        let options = ExtractOptions::default().overwrite(false);
+
+        // Act
+        let result =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options);
+
+        // Assert
+        assert!(result.is_err());
+        let content = fs::read_to_string(archive.target_path.join("a.txt")).unwrap();
+        assert_eq!(content, "existing");
+    }
+
+    #[test]
+    fn extract_with_options_enforces_limits() {
+        // Arrange
+        let archive = create_archive(&[("a.txt", "0123456789"), ("b.txt", "0123456789")]);
+        let too_many_entries =
+            ExtractOptions::default().limits(ExtractLimits::default().max_entries(1));
+        let too_large =
+            ExtractOptions::default().limits(ExtractLimits::default().max_total_size(15));
+
+        // Act
+        let entries_result = zip_extract_with_options(
+            &archive.archive_path,
+            &archive.target_path,
+            &too_many_entries,
+        );
+        let size_result =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &too_large);
+
+        // Assert
+        assert!(entries_result.is_err());
+        assert!(size_result.is_err());
+        assert!(!archive.target_path.join("b.txt").exists());
+    }
+
+    #[test]
+    fn extract_with_options_writes_files_with_long_names() {
+        // Arrange
+        let name = format!("{}.txt", "a".repeat(240));
+        let archive = create_archive(&[(&name, "long")]);
+
+        // Act
+        let report = zip_extract_with_options(
+            &archive.archive_path,
+            &archive.target_path,
+            &ExtractOptions::default(),
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(report.written.len(), 1);
+        let content = fs::read_to_string(archive.target_path.join(&name)).unwrap();
+        assert_eq!(content, "long");
+    }
+
+    fn create_archive(entries: &[(&str, &str)]) -> TestArchive {
+        TestArchive::create(|zip_writer| {
+            let options = SimpleFileOptions::default();
+            for (name, content) in entries {
+                if let Some((parent, _)) = name.rsplit_once('/') {
+                    zip_writer.add_directory(parent, options).unwrap();
+                }
+                zip_writer.start_file(*name, options).unwrap();
+                zip_writer.write_all(content.as_bytes()).unwrap();
+            }
+        })
+    }
+}
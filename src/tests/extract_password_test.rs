@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::extract_password::PasswordError;
+    use crate::inflate::zip_archive_extensions::ZipArchiveExtensions;
+    use crate::inflate::zip_extract::{
+        zip_extract_file_to_memory_with_password, zip_extract_with_options,
+        zip_extract_with_password,
+    };
+    use crate::tests::test_archive::TestArchive;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+    use tempfile::tempdir;
+    use zip::write::{FullFileOptions, SimpleFileOptions};
+    use zip::{AesMode, ZipArchive, ZipWriter};
+
+    #[test]
+    fn extract_with_password_decrypts_encrypted_entries() {
+        // Arrange
+        let archive = create_archive();
+
+        // Act
+        zip_extract_with_password(&archive.archive_path, &archive.target_path, b"secret").unwrap();
+
+        // Assert
+        let target = &archive.target_path;
+        assert_eq!(
+            fs::read_to_string(target.join("plain.txt")).unwrap(),
+            "plain"
+        );
+        assert_eq!(fs::read_to_string(target.join("aes.txt")).unwrap(), "aes");
+        assert_eq!(
+            fs::read_to_string(target.join("aes128.txt")).unwrap(),
+            "aes128"
+        );
+    }
+
+    #[test]
+    fn extract_fails_with_password_required_without_password() {
+        // Arrange
+        let archive = create_archive();
+
+        // Act
+        let error = zip_extract_with_options(
+            &archive.archive_path,
+            &archive.target_path,
+            &ExtractOptions::default(),
+        )
+        .unwrap_err();
+
+        // Assert
+        assert_eq!(
+            PasswordError::from_error(&error),
+            Some(&PasswordError::PasswordRequired {
+                name: PathBuf::from("aes.txt")
+            })
+        );
+    }
+
+    #[test]
+    fn extract_fails_with_wrong_password() {
+        // Arrange
+        let archive = create_archive();
+
+        // Act
+        let error =
+            zip_extract_with_password(&archive.archive_path, &archive.target_path, b"guess")
+                .unwrap_err();
+
+        // Assert
+        assert_eq!(
+            PasswordError::from_error(&error),
+            Some(&PasswordError::WrongPassword {
+                name: PathBuf::from("aes.txt")
+            })
+        );
+    }
+
+    #[test]
+    fn extract_with_password_provider_uses_password_per_entry() {
+        // Arrange
+        let tmp = tempdir().unwrap();
+        let archive_path = tmp.path().join("archive.zip");
+        let mut zip_writer = ZipWriter::new(File::create(&archive_path).unwrap());
+        for (name, password) in [("a.txt", "first"), ("b.txt", "second")] {
+            let options =
+                SimpleFileOptions::default().with_aes_encryption(AesMode::Aes256, password);
+            zip_writer.start_file(name, options).unwrap();
+            zip_writer.write_all(name.as_bytes()).unwrap();
+        }
+        zip_writer.finish().unwrap();
+        let options =
+            ExtractOptions::default().password_provider(|name: &Path| match name.to_str() {
+                Some("a.txt") => Some(b"first".to_vec()),
+                Some("b.txt") => Some(b"second".to_vec()),
+                _ => None,
+            });
+
+        // Act
+        zip_extract_with_options(&archive_path, tmp.path(), &options).unwrap();
+
+        // Assert
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("a.txt")).unwrap(),
+            "a.txt"
+        );
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("b.txt")).unwrap(),
+            "b.txt"
+        );
+    }
+
+    #[test]
+    fn extract_skips_filtered_encrypted_entries_without_password() {
+        // Arrange
+        let archive = create_archive();
+        let options = ExtractOptions::default().entry_filter(|name| name == Path::new("plain.txt"));
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        assert!(archive.target_path.join("plain.txt").is_file());
+        assert!(!archive.target_path.join("aes.txt").exists());
+    }
+
+    #[test]
+    fn extract_file_to_memory_with_password_decrypts_entry() {
+        // Arrange
+        let archive = create_archive();
+        let mut buffer = Vec::new();
+
+        // Act
+        zip_extract_file_to_memory_with_password(
+            &archive.archive_path,
+            &PathBuf::from("aes128.txt"),
+            &mut buffer,
+            b"secret",
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(buffer, b"aes128");
+    }
+
+    #[test]
+    fn extract_file_to_memory_fails_for_unsupported_encryption() {
+        // Arrange
+        let tmp = tempdir().unwrap();
+        let archive_path = tmp.path().join("archive.zip");
+        let mut zip_writer = ZipWriter::new(File::create(&archive_path).unwrap());
+        let mut options = FullFileOptions::default().with_aes_encryption(AesMode::Aes256, "secret");
+        options.add_extra_data(0x6666, [0u8; 8], false).unwrap();
+        zip_writer.start_file("strong.txt", options).unwrap();
+        zip_writer.write_all(b"strong").unwrap();
+        zip_writer.finish().unwrap();
+        // The strong encryption header ID is reserved by the writer, so the placeholder field is
+        // renamed in place.
+        let content = fs::read(&archive_path).unwrap();
+        let content = replace_all(&content, &[0x66, 0x66, 8, 0], &[0x17, 0x00, 8, 0]);
+        fs::write(&archive_path, content).unwrap();
+        let mut archive = ZipArchive::new(File::open(&archive_path).unwrap()).unwrap();
+        let mut buffer = Vec::new();
+
+        // Act
+        let error = archive
+            .extract_file_to_memory_with_password(0, &mut buffer, b"secret")
+            .unwrap_err();
+
+        // Assert
+        assert_eq!(
+            PasswordError::from_error(&error),
+            Some(&PasswordError::UnsupportedEncryption {
+                name: PathBuf::from("strong.txt")
+            })
+        );
+    }
+
+    fn replace_all(content: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+        let mut result = content.to_vec();
+        for i in 0..=result.len() - from.len() {
+            if result[i..i + from.len()] == *from {
+                result[i..i + to.len()].copy_from_slice(to);
+            }
+        }
+        result
+    }
+
+    fn create_archive() -> TestArchive {
+        TestArchive::create(|zip_writer| {
+            let plain = SimpleFileOptions::default();
+            zip_writer.start_file("plain.txt", plain).unwrap();
+            zip_writer.write_all(b"plain").unwrap();
+            let aes = plain.with_aes_encryption(AesMode::Aes256, "secret");
+            zip_writer.start_file("aes.txt", aes).unwrap();
+            zip_writer.write_all(b"aes").unwrap();
+            let aes128 = plain.with_aes_encryption(AesMode::Aes128, "secret");
+            zip_writer.start_file("aes128.txt", aes128).unwrap();
+            zip_writer.write_all(b"aes128").unwrap();
+        })
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::extraction_report::SkipReason;
+    use crate::inflate::zip_extract::zip_extract_with_options;
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract_strips_leading_components() {
+        // Arrange
+        let archive = create_archive(&[
+            "project-1.2.3/",
+            "project-1.2.3/README.md",
+            "project-1.2.3/docs/guide.md",
+        ]);
+        let options = ExtractOptions::default().strip_components(1);
+
+        // Act
+        let report =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)
+                .unwrap();
+
+        // Assert
+        assert_eq!(
+            fs::read_to_string(archive.target_path.join("README.md")).unwrap(),
+            "project-1.2.3/README.md"
+        );
+        assert!(archive.target_path.join("docs/guide.md").exists());
+        assert!(!archive.target_path.join("project-1.2.3").exists());
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].reason, SkipReason::OutsideSubtree);
+    }
+
+    #[test]
+    fn extract_only_extracts_entries_below_prefix() {
+        // Arrange
+        let archive = create_archive(&[
+            "project-1.2.3/README.md",
+            "project-1.2.3/docs/guide.md",
+            "project-1.2.3/docs-old/guide.md",
+        ]);
+        let options = ExtractOptions::default()
+            .prefix("project-1.2.3/docs")
+            .strip_components(2);
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        let extracted: Vec<PathBuf> = fs::read_dir(&archive.target_path)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into())
+            .collect();
+        assert_eq!(extracted, vec![PathBuf::from("guide.md")]);
+        assert_eq!(
+            fs::read_to_string(archive.target_path.join("guide.md")).unwrap(),
+            "project-1.2.3/docs/guide.md"
+        );
+    }
+
+    #[test]
+    fn extract_does_not_escape_target_when_stripping_parent_components() {
+        // Arrange
+        let archive = create_archive(&["a/b/../../c.txt", "a/b/../../../escape.txt"]);
+        let options = ExtractOptions::default().strip_components(1);
+
+        // Act
+        let report =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)
+                .unwrap();
+
+        // Assert
+        let root = archive.target_path.parent().unwrap();
+        assert!(!root.join("c.txt").exists());
+        assert!(!root.join("escape.txt").exists());
+        assert_eq!(fs::read_dir(&archive.target_path).unwrap().count(), 0);
+        let mut reasons: Vec<SkipReason> = report.skipped.into_iter().map(|s| s.reason).collect();
+        reasons.sort_by_key(|r| format!("{:?}", r));
+        assert_eq!(
+            reasons,
+            vec![SkipReason::OutsideSubtree, SkipReason::UnsafePath]
+        );
+    }
+
+    fn create_archive(names: &[&str]) -> TestArchive {
+        let options = SimpleFileOptions::default();
+        TestArchive::create(|zip_writer| {
+            for name in names {
+                if name.ends_with('/') {
+                    zip_writer.add_directory(*name, options).unwrap();
+                } else {
+                    zip_writer.start_file(*name, options).unwrap();
+                    zip_writer.write_all(name.as_bytes()).unwrap();
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::extraction_report::{EntryKind, SkipReason};
+    use crate::inflate::zip_extract::{
+        zip_extract, zip_extract_parallel, zip_extract_with_options,
+    };
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use zip::CompressionMethod;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract_reports_written_and_skipped_entries() {
+        // Arrange
+        let archive = create_archive(false);
+
+        // Act
+        let report = zip_extract(&archive.archive_path, &archive.target_path).unwrap();
+
+        // Assert
+        let written: Vec<(PathBuf, EntryKind, u64)> = report
+            .written
+            .iter()
+            .map(|entry| (entry.path.clone(), entry.kind, entry.bytes))
+            .collect();
+        assert_eq!(
+            written,
+            vec![
+                (PathBuf::from("data"), EntryKind::Directory, 0),
+                (PathBuf::from("data/a.txt"), EntryKind::File, 5),
+                (PathBuf::from("data/b.txt"), EntryKind::File, 8),
+            ]
+        );
+        assert_eq!(report.bytes_written, 13);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].name, PathBuf::from("../escape.txt"));
+        assert_eq!(report.skipped[0].reason, SkipReason::UnsafePath);
+        assert!(report.failed.is_empty());
+    }
+
+    #[test]
+    fn extract_reports_relocated_paths() {
+        // Arrange
+        let archive = create_archive(false);
+        let options = ExtractOptions::default().strip_components(1);
+
+        // Act
+        let report =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)
+                .unwrap();
+
+        // Assert
+        let file = &report.written[0];
+        assert_eq!(file.name, PathBuf::from("data/a.txt"));
+        assert_eq!(file.path, PathBuf::from("a.txt"));
+        assert!(archive.target_path.join(&file.path).is_file());
+    }
+
+    #[test]
+    fn extract_stops_at_corrupt_entry_by_default() {
+        // Arrange
+        let archive = create_archive(true);
+
+        // Act
+        let result = zip_extract_with_options(
+            &archive.archive_path,
+            &archive.target_path,
+            &ExtractOptions::default(),
+        );
+
+        // Assert
+        assert!(result.is_err());
+        assert!(!archive.target_path.join("data/b.txt").exists());
+    }
+
+    #[test]
+    fn extract_with_continue_on_error_reports_failed_entries() {
+        // Arrange
+        let archive = create_archive(true);
+        let options = ExtractOptions::default().continue_on_error(true);
+
+        // Act
+        let report =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)
+                .unwrap();
+
+        // Assert
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].name, PathBuf::from("data/a.txt"));
+        assert_ne!(report.failed[0].message, "i/o error");
+        assert!(report.failed[0].message.contains("checksum"));
+        assert!(!archive.target_path.join("data/a.txt").exists());
+        assert_eq!(
+            fs::read_to_string(archive.target_path.join("data/b.txt")).unwrap(),
+            "contents"
+        );
+        assert_eq!(report.bytes_written, 8);
+    }
+
+    #[test]
+    fn extract_parallel_with_continue_on_error_reports_failed_entries() {
+        // Arrange
+        let archive = create_archive(true);
+        let options = ExtractOptions::default().continue_on_error(true);
+
+        // Act
+        let report =
+            zip_extract_parallel(&archive.archive_path, &archive.target_path, &options, 2).unwrap();
+
+        // Assert
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].name, PathBuf::from("data/a.txt"));
+        assert!(!archive.target_path.join("data/a.txt").exists());
+        assert!(archive.target_path.join("data/b.txt").is_file());
+    }
+
+    /// Creates an archive with a directory, two stored files and an entry with an unsafe path. If
+    /// `corrupt` is set, the data of `data/a.txt` is altered, so that its CRC32 does not match.
+    fn create_archive(corrupt: bool) -> TestArchive {
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let archive = TestArchive::create(|zip_writer| {
+            zip_writer.add_directory("data/", options).unwrap();
+            zip_writer.start_file("data/a.txt", options).unwrap();
+            zip_writer.write_all(b"hello").unwrap();
+            zip_writer.start_file("data/b.txt", options).unwrap();
+            zip_writer.write_all(b"contents").unwrap();
+            zip_writer.start_file("../escape.txt", options).unwrap();
+            zip_writer.write_all(b"escape").unwrap();
+        });
+
+        if corrupt {
+            let mut content = fs::read(&archive.archive_path).unwrap();
+            let offset = content
+                .windows(5)
+                .position(|window| window == b"hello")
+                .unwrap();
+            content[offset] = b'j';
+            fs::write(&archive.archive_path, content).unwrap();
+        }
+
+        archive
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::integrity::{EntryTestStatus, zip_test};
+    use crate::inflate::zip_archive_extensions::ZipArchiveExtensions;
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::{Cursor, Write};
+    use std::path::PathBuf;
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipArchive};
+
+    const STORED_CONTENT: &[u8] = b"stored content that is checked";
+
+    #[test]
+    fn test_reports_intact_entries() {
+        // Arrange
+        let archive = create_archive();
+
+        // Act
+        let results = zip_test(&archive.archive_path).unwrap();
+
+        // Assert
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|result| result.is_ok()));
+        assert_eq!(results[1].name, PathBuf::from("stored.txt"));
+    }
+
+    #[test]
+    fn test_detects_crc_mismatch() {
+        // Arrange
+        let archive = create_archive();
+        let mut content = fs::read(&archive.archive_path).unwrap();
+        let offset = find(&content, STORED_CONTENT);
+        content[offset] ^= 0xFF;
+
+        // Act
+        let results = ZipArchive::new(Cursor::new(content))
+            .unwrap()
+            .test()
+            .unwrap();
+
+        // Assert
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1].status,
+            EntryTestStatus::CrcMismatch { .. }
+        ));
+        assert!(results[2].is_ok());
+    }
+
+    #[test]
+    fn test_detects_corrupt_compressed_stream() {
+        // Arrange
+        let archive = create_archive();
+        let mut content = fs::read(&archive.archive_path).unwrap();
+        let offset = find(&content, b"deflated.txt") + "deflated.txt".len();
+        for byte in &mut content[offset..offset + 16] {
+            *byte = 0xFF;
+        }
+
+        // Act
+        let results = ZipArchive::new(Cursor::new(content))
+            .unwrap()
+            .test()
+            .unwrap();
+
+        // Assert
+        assert!(!results[2].is_ok());
+        assert!(results[1].is_ok());
+    }
+
+    fn find(content: &[u8], needle: &[u8]) -> usize {
+        content
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .unwrap()
+    }
+
+    fn create_archive() -> TestArchive {
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        TestArchive::without_target(|zip_writer| {
+            zip_writer.add_directory("data", stored).unwrap();
+            zip_writer.start_file("stored.txt", stored).unwrap();
+            zip_writer.write_all(STORED_CONTENT).unwrap();
+            zip_writer.start_file("deflated.txt", deflated).unwrap();
+            zip_writer.write_all(&b"deflated ".repeat(1000)).unwrap();
+        })
+    }
+}
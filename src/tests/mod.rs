@@ -1,10 +1,40 @@
+mod create_encrypted_test;
+mod create_from_directory_test;
+mod create_observer_test;
+mod extract_atomic_test;
+mod extract_conflict_test;
+mod extract_filter_test;
+mod extract_limits_test;
+mod extract_observer_test;
+mod extract_options_test;
+mod extract_password_test;
+mod extract_subtree_test;
+mod extraction_report_test;
+mod integrity_test;
 mod is_zip_test;
+mod parallel_create_test;
+mod parallel_extract_test;
+mod preserve_metadata_test;
 mod preserve_symlinks_test;
+mod prune_ignored_test;
+mod reproducible_create_test;
+mod restore_permissions_test;
+mod restore_symlinks_test;
+mod restore_timestamps_test;
+mod streaming_extract_test;
+mod test_archive;
+mod test_source;
+mod walk_options_test;
+mod zip_ignore_sources_test;
 mod zip_ignore_test;
 
+#[path = "audit/audited_extraction_test.rs"]
+mod audited_extraction_test;
 #[path = "audit/utils/path_checker_abs_test.rs"]
 mod path_checker_abs_test;
 #[path = "audit/utils/path_depth_analyzer_test.rs"]
 mod path_depth_analyzer_test;
 #[path = "audit/utils/windows_reserved_name_checker_test.rs"]
 mod windows_reserved_name_checker_test;
+#[path = "audit/zip_audit_verified_test.rs"]
+mod zip_audit_verified_test;
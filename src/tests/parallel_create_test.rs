@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use crate::default_entry_handler::DefaultEntryHandler;
+    use crate::deflate::walk_options::{SymlinkPolicy, WalkOptions};
+    use crate::deflate::zip_ignore_entry_handler::ZipIgnoreEntryHandler;
+    use crate::deflate::zip_writer::{
+        zip_create_from_directory_parallel, zip_create_from_directory_with_options,
+    };
+    use crate::deflate::zip_writer_extensions::ZipWriterExtensions;
+    use crate::tests::test_source::TestSource;
+    use std::fs::{self, File};
+    use std::io::Read;
+    use std::path::Path;
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+    const SPILLED_FILE_SIZE: usize = 9 * 1024 * 1024;
+
+    #[test]
+    fn create_parallel_matches_sequential_layout() {
+        // Arrange
+        let source = create_source_directory();
+        let sequential_path = source.temp_path("sequential.zip");
+        let parallel_path = source.temp_path("parallel.zip");
+
+        // Act
+        zip_create_from_directory_with_options(&sequential_path, &source.source_path, |_| {
+            options()
+        })
+        .unwrap();
+        zip_create_from_directory_parallel(&parallel_path, &source.source_path, |_| options(), 4)
+            .unwrap();
+
+        // Assert
+        let sequential = read_entries(&sequential_path);
+        let parallel = read_entries(&parallel_path);
+        assert_eq!(
+            parallel.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            sequential.iter().map(|(name, _)| name).collect::<Vec<_>>()
+        );
+        assert_eq!(parallel, sequential);
+        let (_, large) = parallel
+            .iter()
+            .find(|(name, _)| name == "large/spilled.bin")
+            .unwrap();
+        assert_eq!(large.len(), SPILLED_FILE_SIZE);
+    }
+
+    #[test]
+    fn create_parallel_composes_with_handlers() {
+        // Arrange
+        let source = create_source_directory();
+        fs::write(source.source_path.join(".zipignore"), "*.log\nlarge/\n").unwrap();
+        fs::write(source.source_path.join("dir-1/app.log"), "ignored").unwrap();
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+
+        // Act
+        zip_writer
+            .create_from_directory_parallel(
+                &source.source_path,
+                |_| SimpleFileOptions::default(),
+                &ZipIgnoreEntryHandler::new(),
+                &WalkOptions::default(),
+                0,
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+
+        // Assert
+        let names: Vec<String> = read_entries(&source.archive_path)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert!(names.contains(&"dir-1/1.txt".to_string()));
+        assert!(!names.contains(&"dir-1/app.log".to_string()));
+        assert!(!names.iter().any(|name| name.starts_with("large")));
+    }
+
+    #[test]
+    fn create_parallel_matches_sequential_layout_with_walk_options() {
+        // Arrange
+        let source = create_source_directory();
+        fs::create_dir_all(source.source_path.join("dir-0/nested/deeper")).unwrap();
+        fs::write(source.source_path.join("dir-0/nested/deeper/a.txt"), "a").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("dir-1", source.source_path.join("link")).unwrap();
+        let walk_options = WalkOptions::default()
+            .symlinks(SymlinkPolicy::DontFollow)
+            .max_depth(3);
+
+        // Act
+        let sequential = create_archive(&source, "sequential.zip", |zip_writer| {
+            zip_writer.create_from_directory_with_walk_options(
+                &source.source_path,
+                |_| options(),
+                &DefaultEntryHandler,
+                &walk_options,
+            )
+        });
+        let parallel = create_archive(&source, "parallel.zip", |zip_writer| {
+            zip_writer.create_from_directory_parallel(
+                &source.source_path,
+                |_| options(),
+                &DefaultEntryHandler,
+                &walk_options,
+                4,
+            )
+        });
+
+        // Assert
+        let names: Vec<String> = parallel.iter().map(|(name, _)| name.clone()).collect();
+        assert_eq!(parallel, sequential);
+        assert!(names.contains(&"dir-0/nested/deeper/".to_string()));
+        assert!(!names.contains(&"dir-0/nested/deeper/a.txt".to_string()));
+        #[cfg(unix)]
+        assert!(!names.contains(&"link/1.txt".to_string()));
+    }
+
+    fn create_archive<C>(source: &TestSource, name: &str, create: C) -> Vec<(String, Vec<u8>)>
+    where
+        C: FnOnce(&mut ZipWriter<File>) -> zip::result::ZipResult<()>,
+    {
+        let archive_path = source.temp_path(name);
+        let mut zip_writer = ZipWriter::new(File::create(&archive_path).unwrap());
+        create(&mut zip_writer).unwrap();
+        zip_writer.finish().unwrap();
+        read_entries(&archive_path)
+    }
+
+    fn options() -> SimpleFileOptions {
+        SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(true)
+    }
+
+    fn read_entries(archive_path: &Path) -> Vec<(String, Vec<u8>)> {
+        let mut archive = ZipArchive::new(File::open(archive_path).unwrap()).unwrap();
+        let mut entries = Vec::new();
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).unwrap();
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).unwrap();
+            entries.push((entry.name().to_string(), content));
+        }
+        entries
+    }
+
+    fn create_source_directory() -> TestSource {
+        let source = TestSource::new();
+        for i in 0..50 {
+            let content = format!("file {} ", i).repeat(i * 10 + 1);
+            source.write(&format!("dir-{}/{}.txt", i % 5, i), content);
+        }
+        let large: Vec<u8> = (0..SPILLED_FILE_SIZE).map(|i| (i % 251) as u8).collect();
+        source.write("large/spilled.bin", large);
+        source
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_limits::{ExtractLimitError, ExtractLimits};
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::zip_extract::zip_extract_parallel;
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::{ErrorKind, Write};
+    use zip::CompressionMethod;
+    use zip::result::ZipError;
+    use zip::write::SimpleFileOptions;
+
+    const ENTRY_COUNT: usize = 200;
+
+    #[test]
+    fn extract_parallel_extracts_all_entries() {
+        // Arrange
+        let archive = create_archive(&[]);
+
+        // Act
+        zip_extract_parallel(
+            &archive.archive_path,
+            &archive.target_path,
+            &ExtractOptions::default(),
+            4,
+        )
+        .unwrap();
+
+        // Assert
+        for i in 0..ENTRY_COUNT {
+            let path = archive.target_path.join(entry_name(i));
+            assert_eq!(fs::read_to_string(path).unwrap(), entry_content(i));
+        }
+        assert!(archive.target_path.join("dir-0/empty").is_dir());
+    }
+
+    #[test]
+    fn extract_parallel_keeps_last_entry_for_duplicate_paths() {
+        // Arrange
+        let archive = create_archive(&[("dir-0/./0.txt", "first"), ("dir-0/x/../0.txt", "last")]);
+
+        // Act
+        zip_extract_parallel(
+            &archive.archive_path,
+            &archive.target_path,
+            &ExtractOptions::default(),
+            4,
+        )
+        .unwrap();
+
+        // Assert
+        let content = fs::read_to_string(archive.target_path.join("dir-0/0.txt")).unwrap();
+        assert_eq!(content, "last");
+    }
+
+    #[test]
+    fn extract_parallel_fails_before_writing_for_duplicate_paths_without_overwrite() {
+        // Arrange
+        let archive = create_archive(&[("dir-0/./0.txt", "duplicate")]);
+        let options = ExtractOptions::default().overwrite(false);
+
+        // Act
+        let error = zip_extract_parallel(&archive.archive_path, &archive.target_path, &options, 4)
+            .unwrap_err();
+
+        // Assert
+        assert!(matches!(error, ZipError::Io(ref e) if e.kind() == ErrorKind::AlreadyExists));
+        assert!(!archive.target_path.join("dir-1/1.txt").exists());
+    }
+
+    #[test]
+    fn extract_parallel_removes_output_if_limit_is_exceeded() {
+        // Arrange
+        let archive = create_archive(&[]);
+        let options =
+            ExtractOptions::default().limits(ExtractLimits::default().max_total_size(1024));
+
+        // Act
+        let error = zip_extract_parallel(&archive.archive_path, &archive.target_path, &options, 4)
+            .unwrap_err();
+
+        // Assert
+        assert_eq!(
+            ExtractLimitError::from_error(&error),
+            Some(&ExtractLimitError::TotalSizeExceeded { limit: 1024 })
+        );
+        assert_eq!(fs::read_dir(&archive.target_path).unwrap().count(), 0);
+    }
+
+    fn entry_name(i: usize) -> String {
+        format!("dir-{}/{}.txt", i % 10, i)
+    }
+
+    fn entry_content(i: usize) -> String {
+        format!("entry {} ", i).repeat(i + 1)
+    }
+
+    fn create_archive(extra_entries: &[(&str, &str)]) -> TestArchive {
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        TestArchive::create(|zip_writer| {
+            zip_writer.add_directory("dir-0/empty", options).unwrap();
+            for i in 0..ENTRY_COUNT {
+                zip_writer.start_file(entry_name(i), options).unwrap();
+                zip_writer.write_all(entry_content(i).as_bytes()).unwrap();
+            }
+            for (name, content) in extra_entries {
+                zip_writer.start_file(*name, options).unwrap();
+                zip_writer.write_all(content.as_bytes()).unwrap();
+            }
+        })
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    use crate::deflate::preserve_metadata_handler::PreserveMetadataHandler;
+    use crate::deflate::zip_writer_extensions::ZipWriterExtensions;
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::zip_extract::zip_extract_with_options;
+    use crate::tests::test_source::TestSource;
+    use std::fs::{self, File};
+    use std::path::Path;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use zip::extra_fields::ExtraField;
+    use zip::write::{FullFileOptions, SimpleFileOptions};
+    use zip::{DateTime, ZipArchive, ZipWriter};
+
+    const MODIFIED_SECONDS: u64 = 1_600_000_001; // 2020-09-13 12:26:41
+
+    #[test]
+    fn create_with_full_options_stores_extended_timestamp() {
+        // Arrange
+        let source = create_source();
+
+        // Act
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+        zip_writer
+            .create_from_directory_with_options(
+                &source.source_path,
+                |_| FullFileOptions::default(),
+                &PreserveMetadataHandler::new(),
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+
+        // Assert
+        let mut archive = ZipArchive::new(File::open(&source.archive_path).unwrap()).unwrap();
+        let entry = archive.by_name("dir/file.txt").unwrap();
+        assert_eq!(
+            entry.last_modified(),
+            Some(DateTime::from_date_and_time(2020, 9, 13, 12, 26, 40).unwrap())
+        );
+        let extended_modified = entry.extra_data_fields().find_map(|field| match field {
+            ExtraField::ExtendedTimestamp(timestamp) => timestamp.mod_time(),
+            _ => None,
+        });
+        assert_eq!(extended_modified, Some(MODIFIED_SECONDS as u32));
+    }
+
+    #[test]
+    fn create_with_simple_options_stores_dos_timestamp() {
+        // Arrange
+        let source = create_source();
+
+        // Act
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+        zip_writer
+            .create_from_directory_with_options(
+                &source.source_path,
+                |_| SimpleFileOptions::default(),
+                &PreserveMetadataHandler::new(),
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+
+        // Assert
+        let mut archive = ZipArchive::new(File::open(&source.archive_path).unwrap()).unwrap();
+        let entry = archive.by_name("dir/file.txt").unwrap();
+        assert_eq!(
+            entry.last_modified(),
+            Some(DateTime::from_date_and_time(2020, 9, 13, 12, 26, 40).unwrap())
+        );
+        assert_eq!(entry.extra_data_fields().count(), 0);
+    }
+
+    #[test]
+    fn create_stores_modified_time_before_1970_as_signed_value() {
+        // Arrange
+        let source = create_source();
+        let file_path = source.source_path.join("dir/file.txt");
+        set_modified(&file_path, UNIX_EPOCH - Duration::from_secs(86_400));
+        fs::create_dir_all(&source.target_path).unwrap();
+        let options = ExtractOptions::default().restore_timestamps(true);
+
+        // Act
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+        zip_writer
+            .create_from_directory_with_options(
+                &source.source_path,
+                |_| FullFileOptions::default(),
+                &PreserveMetadataHandler::new(),
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+        zip_extract_with_options(&source.archive_path, &source.target_path, &options).unwrap();
+
+        // Assert
+        let mut archive = ZipArchive::new(File::open(&source.archive_path).unwrap()).unwrap();
+        let entry = archive.by_name("dir/file.txt").unwrap();
+        let extended_modified = entry.extra_data_fields().find_map(|field| match field {
+            ExtraField::ExtendedTimestamp(timestamp) => timestamp.mod_time(),
+            _ => None,
+        });
+        assert_eq!(
+            extended_modified.map(|seconds| seconds as i32),
+            Some(-86_400)
+        );
+        assert_eq!(
+            fs::metadata(source.target_path.join("dir/file.txt"))
+                .unwrap()
+                .modified()
+                .unwrap(),
+            UNIX_EPOCH - Duration::from_secs(86_400)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn create_preserves_metadata_for_round_trip() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Arrange
+        let source = create_source();
+        fs::create_dir_all(&source.target_path).unwrap();
+        let options = ExtractOptions::default()
+            .restore_timestamps(true)
+            .restore_permissions(true);
+
+        // Act
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+        zip_writer
+            .create_from_directory_with_options(
+                &source.source_path,
+                |_| FullFileOptions::default(),
+                &PreserveMetadataHandler::new(),
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+        zip_extract_with_options(&source.archive_path, &source.target_path, &options).unwrap();
+
+        // Assert
+        let file_path = source.target_path.join("dir/file.txt");
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(
+            fs::metadata(&file_path).unwrap().modified().unwrap(),
+            UNIX_EPOCH + Duration::from_secs(MODIFIED_SECONDS)
+        );
+        assert_eq!(mode(&file_path), 0o640);
+        assert_eq!(mode(&source.target_path.join("dir")), 0o750);
+    }
+
+    fn create_source() -> TestSource {
+        let source = TestSource::new();
+        source.write("dir/file.txt", "content");
+        let file_path = source.source_path.join("dir/file.txt");
+        set_modified(
+            &file_path,
+            UNIX_EPOCH + Duration::from_secs(MODIFIED_SECONDS),
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&file_path, fs::Permissions::from_mode(0o640)).unwrap();
+            fs::set_permissions(
+                source.source_path.join("dir"),
+                fs::Permissions::from_mode(0o750),
+            )
+            .unwrap();
+        }
+        source
+    }
+
+    fn set_modified(path: &Path, modified: SystemTime) {
+        let file = File::options().write(true).open(path).unwrap();
+        file.set_modified(modified).unwrap();
+    }
+}
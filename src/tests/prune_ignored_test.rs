@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use crate::deflate::create_observer::{CreateObserver, ObservingEntryHandler};
+    use crate::deflate::default_entry_handler::DefaultEntryHandler;
+    use crate::deflate::entry_handler::{EntryHandler, EntryOutcome};
+    use crate::deflate::walk_options::WalkOptions;
+    use crate::deflate::zip_ignore_entry_handler::ZipIgnoreEntryHandler;
+    use crate::deflate::zip_writer_extensions::ZipWriterExtensions;
+    use crate::tests::test_source::{TestSource, entry_names};
+    use std::fs::File;
+    use std::io;
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+    use zip::ZipWriter;
+    use zip::result::ZipResult;
+    use zip::write::{FileOptions, SimpleFileOptions};
+
+    #[derive(Default)]
+    struct VisitedPaths {
+        paths: Mutex<Vec<PathBuf>>,
+    }
+
+    impl CreateObserver for VisitedPaths {
+        fn entry_visited(&self, entry_path: &Path) {
+            self.paths.lock().unwrap().push(entry_path.to_path_buf());
+        }
+    }
+
+    /// Prunes every directory named `target`.
+    struct PruneTargetHandler;
+
+    impl EntryHandler<()> for PruneTargetHandler {
+        fn handle_entry<W: Write + io::Seek>(
+            &self,
+            writer: &mut ZipWriter<W>,
+            root: &PathBuf,
+            entry_path: &PathBuf,
+            file_options: FileOptions<()>,
+        ) -> ZipResult<EntryOutcome> {
+            if entry_path.file_name().is_some_and(|name| name == "target") {
+                return Ok(EntryOutcome::Pruned);
+            }
+            DefaultEntryHandler.handle_entry(writer, root, entry_path, file_options)
+        }
+    }
+
+    #[test]
+    fn create_does_not_visit_entries_of_ignored_directories() {
+        // Arrange
+        let source = create_source();
+        let visited = VisitedPaths::default();
+        let handler = ObservingEntryHandler::new(ZipIgnoreEntryHandler::new(), &visited);
+
+        // Act
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+        zip_writer
+            .create_from_directory_with_options(
+                &source.source_path,
+                |_| SimpleFileOptions::default(),
+                &handler,
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+
+        // Assert
+        let ignored = source.source_path.join("node_modules");
+        let visited = visited.paths.into_inner().unwrap();
+        assert!(visited.contains(&ignored));
+        assert!(
+            !visited
+                .iter()
+                .any(|path| path.starts_with(&ignored) && path != &ignored)
+        );
+        assert_eq!(entry_names(&source.archive_path), expected_names());
+    }
+
+    #[test]
+    fn create_parallel_does_not_visit_entries_of_ignored_directories() {
+        // Arrange
+        let source = create_source();
+        let visited = VisitedPaths::default();
+        let handler = ObservingEntryHandler::new(ZipIgnoreEntryHandler::new(), &visited);
+
+        // Act
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+        zip_writer
+            .create_from_directory_parallel(
+                &source.source_path,
+                |_| SimpleFileOptions::default(),
+                &handler,
+                &WalkOptions::default(),
+                4,
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+
+        // Assert
+        let ignored = source.source_path.join("node_modules");
+        let visited = visited.paths.into_inner().unwrap();
+        assert!(
+            !visited
+                .iter()
+                .any(|path| path.starts_with(&ignored) && path != &ignored)
+        );
+        assert_eq!(entry_names(&source.archive_path), expected_names());
+    }
+
+    #[test]
+    fn create_skips_subtrees_pruned_by_custom_handler() {
+        // Arrange
+        let source = create_source();
+
+        // Act
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+        zip_writer
+            .create_from_directory_with_options(
+                &source.source_path,
+                |_| SimpleFileOptions::default(),
+                &PruneTargetHandler,
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+
+        // Assert
+        let names = entry_names(&source.archive_path);
+        assert!(names.contains(&"node_modules/pkg-0/index.js".to_string()));
+        assert!(!names.iter().any(|name| name.starts_with("src/target")));
+    }
+
+    /// Returns the sorted names of the entries that are not ignored.
+    fn expected_names() -> Vec<String> {
+        let mut names = vec![".zipignore".to_string(), "src/".to_string()];
+        names.extend((0..20).map(|i| format!("src/{i}.rs")));
+        names.extend(["src/target/".to_string(), "src/target/out.bin".to_string()]);
+        names.sort();
+        names
+    }
+
+    fn create_source() -> TestSource {
+        let source = TestSource::new();
+        source.write(".zipignore", "node_modules/\n");
+        source.write("src/target/out.bin", "out");
+        for i in 0..20 {
+            source.write(&format!("src/{i}.rs"), "fn main() {}");
+            source.write(
+                &format!("node_modules/pkg-{i}/index.js"),
+                "module.exports = {};",
+            );
+        }
+        source
+    }
+}
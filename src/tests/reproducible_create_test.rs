@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use crate::default_entry_handler::DefaultEntryHandler;
+    use crate::deflate::reproducible::{
+        ReproducibleOptions, zip_create_from_directory_reproducible,
+    };
+    use crate::deflate::zip_writer_extensions::ZipWriterExtensions;
+    use std::fs::{self, File};
+    use std::io::ErrorKind;
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, SystemTime};
+    use tempfile::tempdir;
+    use zip::result::ZipError;
+    use zip::write::SimpleFileOptions;
+    use zip::{DateTime, ZipArchive, ZipWriter};
+
+    #[test]
+    fn create_reproducible_writes_identical_archives_for_identical_trees() {
+        // Arrange
+        let tmp = tempdir().unwrap();
+        let first = tmp.path().join("first");
+        let second = tmp.path().join("second");
+        create_tree(
+            &first,
+            &["a.txt", "b/x.txt", "b/y.txt", "c.txt"],
+            1_000_000_000,
+        );
+        create_tree(
+            &second,
+            &["c.txt", "b/y.txt", "b/x.txt", "a.txt"],
+            1_600_000_000,
+        );
+        let first_archive = tmp.path().join("first.zip");
+        let second_archive = tmp.path().join("second.zip");
+
+        // Act
+        zip_create_from_directory_reproducible(&first_archive, &first).unwrap();
+        zip_create_from_directory_reproducible(&second_archive, &second).unwrap();
+
+        // Assert
+        assert_eq!(
+            fs::read(&first_archive).unwrap(),
+            fs::read(&second_archive).unwrap()
+        );
+    }
+
+    #[test]
+    fn create_reproducible_sorts_entries_and_normalizes_metadata() {
+        // Arrange
+        let tmp = tempdir().unwrap();
+        let source = tmp.path().join("source");
+        create_tree(&source, &["c.txt", "b/x.txt", "a.txt"], 1_000_000_000);
+        let archive_path = tmp.path().join("archive.zip");
+        let options = ReproducibleOptions::new().source_date_epoch(1_700_000_000);
+
+        // Act
+        let mut zip_writer = ZipWriter::new(File::create(&archive_path).unwrap());
+        zip_writer
+            .create_from_directory_reproducible(
+                &source,
+                |_| SimpleFileOptions::default(),
+                &DefaultEntryHandler,
+                &options,
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+
+        // Assert
+        let expected_time = DateTime::from_date_and_time(2023, 11, 14, 22, 13, 20).unwrap();
+        let mut archive = ZipArchive::new(File::open(&archive_path).unwrap()).unwrap();
+        let mut entries = Vec::new();
+        for i in 0..archive.len() {
+            let entry = archive.by_index(i).unwrap();
+            assert_eq!(entry.last_modified(), Some(expected_time));
+            entries.push((entry.name().to_string(), entry.unix_mode().unwrap() & 0o777));
+        }
+        assert_eq!(
+            entries,
+            vec![
+                ("a.txt".to_string(), 0o644),
+                ("b/".to_string(), 0o755),
+                ("c.txt".to_string(), 0o644),
+                ("b/x.txt".to_string(), 0o644),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn create_reproducible_keeps_executable_bit() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Arrange
+        let tmp = tempdir().unwrap();
+        let source = tmp.path().join("source");
+        create_tree(&source, &["run.sh", "data.txt"], 1_000_000_000);
+        fs::set_permissions(source.join("run.sh"), fs::Permissions::from_mode(0o700)).unwrap();
+        fs::set_permissions(source.join("data.txt"), fs::Permissions::from_mode(0o600)).unwrap();
+        let archive_path = tmp.path().join("archive.zip");
+
+        // Act
+        zip_create_from_directory_reproducible(&archive_path, &source).unwrap();
+
+        // Assert
+        let mut archive = ZipArchive::new(File::open(&archive_path).unwrap()).unwrap();
+        let data_mode = archive.by_name("data.txt").unwrap().unix_mode().unwrap();
+        let run_mode = archive.by_name("run.sh").unwrap().unix_mode().unwrap();
+        assert_eq!(data_mode & 0o777, 0o644);
+        assert_eq!(run_mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn source_date_epoch_is_parsed_and_clamped() {
+        // Act
+        let unset = ReproducibleOptions::from_source_date_epoch_value(None).unwrap();
+        let parsed = ReproducibleOptions::from_source_date_epoch_value(Some("1700000001")).unwrap();
+        let early = ReproducibleOptions::from_source_date_epoch_value(Some("0")).unwrap();
+        let error =
+            ReproducibleOptions::from_source_date_epoch_value(Some("yesterday")).unwrap_err();
+
+        // Assert
+        assert_eq!(unset.timestamp, DateTime::default());
+        assert_eq!(
+            parsed.timestamp,
+            DateTime::from_date_and_time(2023, 11, 14, 22, 13, 20).unwrap()
+        );
+        assert_eq!(early.timestamp, DateTime::default());
+        assert!(matches!(error, ZipError::Io(ref e) if e.kind() == ErrorKind::InvalidInput));
+    }
+
+    /// Creates the given files below `root`, in the given order, with their names as content, and
+    /// sets their modification times relative to `seconds`.
+    fn create_tree(root: &Path, files: &[&str], seconds: u64) {
+        for (i, name) in files.iter().enumerate() {
+            let path: PathBuf = root.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, name).unwrap();
+            let file = File::options().write(true).open(&path).unwrap();
+            let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(seconds + i as u64);
+            file.set_modified(modified).unwrap();
+        }
+    }
+}
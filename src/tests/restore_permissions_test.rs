@@ -0,0 +1,151 @@
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::zip_extract::zip_extract_with_options;
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::Write;
+    use std::ops::Deref;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract_restores_permissions_and_strips_special_bits() {
+        // Arrange
+        let archive = create_archive_with_modes();
+
+        let options = ExtractOptions::default().restore_permissions(true);
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        assert_eq!(mode_of(&archive.target_path.join("bin/tool")), 0o755);
+        assert_eq!(mode_of(&archive.target_path.join("bin/setuid")), 0o755);
+        assert_eq!(mode_of(&archive.target_path.join("readonly")), 0o555);
+        assert_eq!(
+            mode_of(&archive.target_path.join("readonly/file.txt")),
+            0o644
+        );
+    }
+
+    #[test]
+    fn extract_with_custom_mask_keeps_special_bits() {
+        // Arrange
+        let archive = create_archive_with_modes();
+        let options = ExtractOptions::default()
+            .restore_permissions(true)
+            .permissions_mask(0o7777);
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        assert_eq!(mode_of(&archive.target_path.join("bin/setuid")), 0o4755);
+    }
+
+    #[test]
+    fn extract_does_not_restore_permissions_by_default() {
+        // Arrange
+        let archive = create_archive_with_modes();
+
+        // Act
+        zip_extract_with_options(
+            &archive.archive_path,
+            &archive.target_path,
+            &ExtractOptions::default(),
+        )
+        .unwrap();
+        let second_run = zip_extract_with_options(
+            &archive.archive_path,
+            &archive.target_path,
+            &ExtractOptions::default(),
+        );
+
+        // Assert
+        assert!(second_run.is_ok());
+        assert_eq!(mode_of(&archive.target_path.join("bin/tool")) & 0o111, 0);
+        assert_ne!(mode_of(&archive.target_path.join("readonly")) & 0o200, 0);
+    }
+
+    /// A `TestArchive` whose extracted read-only directories are made writable again on drop.
+    struct ModesArchive(TestArchive);
+
+    impl Deref for ModesArchive {
+        type Target = TestArchive;
+
+        fn deref(&self) -> &TestArchive {
+            &self.0
+        }
+    }
+
+    impl Drop for ModesArchive {
+        fn drop(&mut self) {
+            // Make read-only directories writable again, so that the temporary directory can be
+            // removed.
+            let readonly = self.target_path.join("readonly");
+            let _ = fs::set_permissions(readonly, fs::Permissions::from_mode(0o755));
+        }
+    }
+
+    fn create_archive_with_modes() -> ModesArchive {
+        let archive = TestArchive::create(|zip_writer| {
+            let options = SimpleFileOptions::default();
+            zip_writer.add_directory("bin", options).unwrap();
+            zip_writer
+                .start_file("bin/tool", options.unix_permissions(0o755))
+                .unwrap();
+            zip_writer.write_all(b"#!/bin/sh").unwrap();
+            zip_writer
+                .start_file("bin/setuid", options.unix_permissions(0o755))
+                .unwrap();
+            zip_writer.write_all(b"#!/bin/sh").unwrap();
+            zip_writer
+                .add_directory("readonly", options.unix_permissions(0o555))
+                .unwrap();
+            zip_writer
+                .start_file("readonly/file.txt", options.unix_permissions(0o644))
+                .unwrap();
+            zip_writer.write_all(b"content").unwrap();
+        });
+
+        set_setuid_bit(&archive.archive_path, "bin/setuid");
+
+        ModesArchive(archive)
+    }
+
+    /// `FileOptions::unix_permissions` discards all bits above `0o777`, so the setuid bit is
+    /// patched into the external attributes of the central directory record instead.
+    fn set_setuid_bit(archive_path: &Path, name: &str) {
+        let mut bytes = fs::read(archive_path).unwrap();
+        let signature = [0x50, 0x4b, 0x01, 0x02];
+        let mut offset = 0;
+        while let Some(position) = bytes[offset..]
+            .windows(4)
+            .position(|w| w == signature)
+            .map(|p| p + offset)
+        {
+            let name_length = u16::from_le_bytes([bytes[position + 28], bytes[position + 29]]);
+            let name_start = position + 46;
+            let entry_name = &bytes[name_start..name_start + name_length as usize];
+            if entry_name == name.as_bytes() {
+                let attributes_offset = position + 38;
+                let mut attributes = u32::from_le_bytes(
+                    bytes[attributes_offset..attributes_offset + 4]
+                        .try_into()
+                        .unwrap(),
+                );
+                attributes |= 0o4000 << 16;
+                bytes[attributes_offset..attributes_offset + 4]
+                    .copy_from_slice(&attributes.to_le_bytes());
+            }
+            offset = position + 4;
+        }
+        fs::write(archive_path, bytes).unwrap();
+    }
+
+    fn mode_of(path: &Path) -> u32 {
+        fs::metadata(path).unwrap().permissions().mode() & 0o7777
+    }
+}
@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::extraction_report::SkipReason;
+    use crate::inflate::zip_extract::{zip_extract_parallel, zip_extract_with_options};
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract_skips_symlinks_by_default() {
+        // Arrange
+        let archive = create_archive_with_symlinks();
+
+        // Act
+        let report = zip_extract_with_options(
+            &archive.archive_path,
+            &archive.target_path,
+            &ExtractOptions::default(),
+        )
+        .unwrap();
+
+        // Assert
+        assert!(archive.target_path.join("data/file.txt").exists());
+        assert!(fs::symlink_metadata(archive.target_path.join("link.txt")).is_err());
+        assert_eq!(report.skipped.len(), 3);
+        assert!(
+            report
+                .skipped
+                .iter()
+                .all(|s| s.reason == SkipReason::Symlink)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_restores_symlinks_inside_root_only() {
+        // Arrange
+        let archive = create_archive_with_symlinks();
+        let options = ExtractOptions::default().restore_symlinks(true);
+
+        // Act
+        let report =
+            zip_extract_with_options(&archive.archive_path, &archive.target_path, &options)
+                .unwrap();
+
+        // Assert
+        let link_path = archive.target_path.join("link.txt");
+        assert!(
+            fs::symlink_metadata(&link_path)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_to_string(&link_path).unwrap(), "content");
+
+        assert!(fs::symlink_metadata(archive.target_path.join("data/escape")).is_err());
+        assert!(fs::symlink_metadata(archive.target_path.join("absolute")).is_err());
+
+        let mut skipped: Vec<&PathBuf> = report.skipped.iter().map(|s| &s.name).collect();
+        skipped.sort();
+        assert_eq!(
+            skipped,
+            vec![&PathBuf::from("absolute"), &PathBuf::from("data/escape")]
+        );
+        assert!(
+            report
+                .skipped
+                .iter()
+                .all(|s| matches!(s.reason, SkipReason::SymlinkEscapesRoot { .. }))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_does_not_write_through_chained_symlinks() {
+        // Arrange
+        let archive = create_archive_with_chained_symlinks(false, true);
+        let options = ExtractOptions::default().restore_symlinks(true);
+
+        // Act
+        let _ = zip_extract_with_options(&archive.archive_path, &archive.target_path, &options);
+
+        // Assert
+        assert_nothing_escaped(&archive);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_parallel_does_not_write_through_chained_symlinks() {
+        for with_file in [true, false] {
+            // Arrange
+            let archive = create_archive_with_chained_symlinks(false, with_file);
+            let options = ExtractOptions::default().restore_symlinks(true);
+
+            // Act
+            let _ = zip_extract_parallel(&archive.archive_path, &archive.target_path, &options, 4);
+
+            // Assert
+            assert_nothing_escaped(&archive);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_removes_symlinks_that_escape_through_later_symlinks() {
+        // Arrange
+        let archive = create_archive_with_chained_symlinks(true, true);
+        let options = ExtractOptions::default().restore_symlinks(true);
+
+        // Act
+        let _ = zip_extract_with_options(&archive.archive_path, &archive.target_path, &options);
+
+        // Assert
+        assert_nothing_escaped(&archive);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_parallel_removes_symlinks_that_escape_through_later_symlinks() {
+        for with_file in [true, false] {
+            // Arrange
+            let archive = create_archive_with_chained_symlinks(true, with_file);
+            let options = ExtractOptions::default().restore_symlinks(true);
+
+            // Act
+            let _ = zip_extract_parallel(&archive.archive_path, &archive.target_path, &options, 4);
+
+            // Assert
+            assert_nothing_escaped(&archive);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_parallel_writes_entries_below_symlinks_like_sequential_extraction() {
+        // Arrange
+        let sequential = create_archive_with_symlinked_directory();
+        let parallel = create_archive_with_symlinked_directory();
+        let options = ExtractOptions::default().restore_symlinks(true);
+
+        // Act
+        let sequential_report =
+            zip_extract_with_options(&sequential.archive_path, &sequential.target_path, &options)
+                .unwrap();
+        let parallel_report =
+            zip_extract_parallel(&parallel.archive_path, &parallel.target_path, &options, 4)
+                .unwrap();
+
+        // Assert
+        assert_eq!(sequential_report.written.len(), 4);
+        assert_eq!(parallel_report.written.len(), 4);
+        for archive in [&sequential, &parallel] {
+            let link = fs::symlink_metadata(archive.target_path.join("link")).unwrap();
+            assert!(link.file_type().is_symlink());
+            let content = fs::read_to_string(archive.target_path.join("real/a.txt")).unwrap();
+            assert_eq!(content, "real");
+        }
+    }
+
+    fn assert_nothing_escaped(archive: &TestArchive) {
+        let outside = archive.target_path.parent().unwrap();
+        assert!(!outside.join("pwned.txt").exists());
+        let l2 = fs::symlink_metadata(archive.target_path.join("l2"));
+        assert!(l2.is_err() || !l2.unwrap().file_type().is_symlink());
+    }
+
+    fn create_archive_with_symlinks() -> TestArchive {
+        TestArchive::create(|zip_writer| {
+            let options = SimpleFileOptions::default();
+            zip_writer.add_directory("data", options).unwrap();
+            zip_writer.start_file("data/file.txt", options).unwrap();
+            zip_writer.write_all(b"content").unwrap();
+            zip_writer
+                .add_symlink("link.txt", "data/file.txt", options)
+                .unwrap();
+            zip_writer
+                .add_symlink("data/escape", "../../outside.txt", options)
+                .unwrap();
+            zip_writer
+                .add_symlink("absolute", "/etc/passwd", options)
+                .unwrap();
+        })
+    }
+
+    /// Creates an archive with a directory, a link to it, and a file that is stored both below the
+    /// link and below the directory, as `PreserveSymlinksHandler` stores a linked directory.
+    fn create_archive_with_symlinked_directory() -> TestArchive {
+        let options = SimpleFileOptions::default();
+        TestArchive::create(|zip_writer| {
+            zip_writer.add_directory("real", options).unwrap();
+            zip_writer.add_symlink("link", "real", options).unwrap();
+            zip_writer.start_file("link/a.txt", options).unwrap();
+            zip_writer.write_all(b"link").unwrap();
+            zip_writer.start_file("real/a.txt", options).unwrap();
+            zip_writer.write_all(b"real").unwrap();
+        })
+    }
+
+    /// Creates an archive whose link `l2` resolves to the parent of the extraction root through
+    /// another link. If `relinked` is set, `l2` is inside the root when it is restored, and only
+    /// escapes it once the link it passes through is replaced by a later entry for the same path.
+    /// If `with_file` is set, the archive also has a file entry below `l2`.
+    fn create_archive_with_chained_symlinks(relinked: bool, with_file: bool) -> TestArchive {
+        TestArchive::create(|zip_writer| {
+            let options = SimpleFileOptions::default();
+            zip_writer.add_directory("d", options).unwrap();
+            if relinked {
+                zip_writer.add_symlink("x", "d", options).unwrap();
+                zip_writer.add_symlink("l2", "x/..", options).unwrap();
+                zip_writer.add_symlink("./x", ".", options).unwrap();
+            } else {
+                zip_writer.add_symlink("d/l1", "..", options).unwrap();
+                zip_writer.add_symlink("l2", "d/l1/..", options).unwrap();
+            }
+            if with_file {
+                zip_writer.start_file("l2/pwned.txt", options).unwrap();
+                zip_writer.write_all(b"pwned").unwrap();
+            }
+        })
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use crate::inflate::extract_options::ExtractOptions;
+    use crate::inflate::zip_extract::zip_extract_with_options;
+    use crate::tests::test_archive::TestArchive;
+    use std::fs;
+    use std::io::Write;
+    use std::path::Path;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use zip::DateTime;
+    use zip::write::{FullFileOptions, SimpleFileOptions};
+
+    const EXTENDED_TIMESTAMP_HEADER_ID: u16 = 0x5455;
+    const EXTENDED_MODIFIED_TIME: u32 = 1_600_000_000;
+
+    #[test]
+    fn extract_restores_timestamps() {
+        // Arrange
+        let archive = create_archive_with_timestamps();
+        let options = ExtractOptions::default().restore_timestamps(true);
+
+        // Act
+        zip_extract_with_options(&archive.archive_path, &archive.target_path, &options).unwrap();
+
+        // Assert
+        let expected_dos_time = UNIX_EPOCH + Duration::from_secs(1_577_934_246); // 2020-01-02 03:04:06
+        let expected_extended_time =
+            UNIX_EPOCH + Duration::from_secs(EXTENDED_MODIFIED_TIME as u64);
+        assert_eq!(
+            modified(&archive.target_path.join("dir/dos.txt")),
+            expected_dos_time
+        );
+        assert_eq!(
+            modified(&archive.target_path.join("dir/extended.txt")),
+            expected_extended_time
+        );
+        assert_eq!(
+            modified(&archive.target_path.join("dir")),
+            expected_dos_time
+        );
+    }
+
+    #[test]
+    fn extract_keeps_current_time_by_default() {
+        // Arrange
+        let archive = create_archive_with_timestamps();
+
+        // Act
+        zip_extract_with_options(
+            &archive.archive_path,
+            &archive.target_path,
+            &ExtractOptions::default(),
+        )
+        .unwrap();
+
+        // Assert
+        let recently = SystemTime::now() - Duration::from_secs(3600);
+        assert!(modified(&archive.target_path.join("dir/dos.txt")) > recently);
+    }
+
+    fn create_archive_with_timestamps() -> TestArchive {
+        let dos_time = DateTime::from_date_and_time(2020, 1, 2, 3, 4, 6).unwrap();
+        let options = SimpleFileOptions::default().last_modified_time(dos_time);
+
+        let mut extended_options = FullFileOptions::default().last_modified_time(dos_time);
+        let mut extended_timestamp = vec![0x01u8];
+        extended_timestamp.extend_from_slice(&EXTENDED_MODIFIED_TIME.to_le_bytes());
+        extended_options
+            .add_extra_data(EXTENDED_TIMESTAMP_HEADER_ID, extended_timestamp, false)
+            .unwrap();
+
+        TestArchive::create(|zip_writer| {
+            zip_writer.add_directory("dir", options).unwrap();
+            zip_writer.start_file("dir/dos.txt", options).unwrap();
+            zip_writer.write_all(b"dos").unwrap();
+            zip_writer
+                .start_file("dir/extended.txt", extended_options)
+                .unwrap();
+            zip_writer.write_all(b"extended").unwrap();
+        })
+    }
+
+    fn modified(path: &Path) -> SystemTime {
+        fs::metadata(path).unwrap().modified().unwrap()
+    }
+}
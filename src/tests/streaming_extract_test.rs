@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use crate::file_utils::file_write_from_reader;
+    use crate::inflate::zip_archive_extensions::ZipArchiveExtensions;
+    use std::fs::{self, File};
+    use std::io::{Cursor, Write};
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+    const LARGE_ENTRY_SIZE: usize = 5 * 1024 * 1024;
+
+    #[test]
+    fn extract_streams_large_entries_to_disk() {
+        // Arrange
+        let tmp = tempdir().unwrap();
+        let archive_path = tmp.path().join("archive.zip");
+        let target_path = tmp.path().join("target");
+        fs::create_dir_all(&target_path).unwrap();
+        let expected = large_content();
+        let mut zip_writer = ZipWriter::new(File::create(&archive_path).unwrap());
+        zip_writer
+            .start_file("large.bin", SimpleFileOptions::default().large_file(true))
+            .unwrap();
+        zip_writer.write_all(&expected).unwrap();
+        zip_writer.finish().unwrap();
+
+        // Act
+        let mut archive = ZipArchive::new(File::open(&archive_path).unwrap()).unwrap();
+        // `ZipArchive::extract` of the zip crate takes precedence over the extension method.
+        let report = ZipArchiveExtensions::extract(&mut archive, &target_path).unwrap();
+        let single_file_path = tmp.path().join("single.bin");
+        archive.extract_file(0, &single_file_path, false).unwrap();
+
+        // Assert
+        assert_eq!(report.bytes_written, LARGE_ENTRY_SIZE as u64);
+        assert_eq!(fs::read(target_path.join("large.bin")).unwrap(), expected);
+        assert_eq!(fs::read(&single_file_path).unwrap(), expected);
+    }
+
+    #[test]
+    fn file_write_from_reader_respects_overwrite_flag() {
+        // Arrange
+        let tmp = tempdir().unwrap();
+        let file_path: PathBuf = tmp.path().join("file.txt");
+        fs::write(&file_path, "existing").unwrap();
+
+        // Act
+        let rejected = file_write_from_reader(file_path.clone(), &mut Cursor::new("new"), false);
+        let written = file_write_from_reader(file_path.clone(), &mut Cursor::new("new"), true);
+
+        // Assert
+        assert!(rejected.is_err());
+        assert_eq!(written.unwrap(), 3);
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "new");
+    }
+
+    #[test]
+    fn extract_file_keeps_existing_file_if_entry_is_corrupt() {
+        // Arrange
+        let tmp = tempdir().unwrap();
+        let archive_path = tmp.path().join("archive.zip");
+        let file_path = tmp.path().join("file.txt");
+        fs::write(&file_path, "existing").unwrap();
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let mut zip_writer = ZipWriter::new(File::create(&archive_path).unwrap());
+        zip_writer.start_file("file.txt", options).unwrap();
+        zip_writer.write_all(b"hello").unwrap();
+        zip_writer.finish().unwrap();
+        let mut content = fs::read(&archive_path).unwrap();
+        let offset = content
+            .windows(5)
+            .position(|window| window == b"hello")
+            .unwrap();
+        content[offset] = b'j';
+        fs::write(&archive_path, content).unwrap();
+
+        // Act
+        let mut archive = ZipArchive::new(File::open(&archive_path).unwrap()).unwrap();
+        let result = archive.extract_file(0, &file_path, true);
+
+        // Assert
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "existing");
+        assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 2);
+    }
+
+    fn large_content() -> Vec<u8> {
+        (0..LARGE_ENTRY_SIZE).map(|i| (i % 251) as u8).collect()
+    }
+}
@@ -0,0 +1,38 @@
+use std::fs::{self, File};
+use std::path::PathBuf;
+use tempfile::{TempDir, tempdir};
+use zip::ZipWriter;
+
+/// An archive in a temporary directory, and the path of a directory to extract it to. Both are
+/// removed when the value is dropped.
+pub(crate) struct TestArchive {
+    _tmp: TempDir,
+    pub(crate) archive_path: PathBuf,
+    pub(crate) target_path: PathBuf,
+}
+
+impl TestArchive {
+    /// Creates an archive with the entries added by `add_entries`, and an empty target directory.
+    pub(crate) fn create(add_entries: impl FnOnce(&mut ZipWriter<File>)) -> Self {
+        let archive = Self::without_target(add_entries);
+        fs::create_dir_all(&archive.target_path).unwrap();
+        archive
+    }
+
+    /// Creates an archive with the entries added by `add_entries`, but not the target directory.
+    pub(crate) fn without_target(add_entries: impl FnOnce(&mut ZipWriter<File>)) -> Self {
+        let tmp = tempdir().unwrap();
+        let archive_path = tmp.path().join("archive.zip");
+        let target_path = tmp.path().join("target");
+
+        let mut zip_writer = ZipWriter::new(File::create(&archive_path).unwrap());
+        add_entries(&mut zip_writer);
+        zip_writer.finish().unwrap();
+
+        TestArchive {
+            _tmp: tmp,
+            archive_path,
+            target_path,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use tempfile::{TempDir, tempdir};
+use zip::ZipArchive;
+
+/// A source directory in a temporary directory, the path of an archive to create from it, and the
+/// path of a directory to extract the archive to. All are removed when the value is dropped.
+pub(crate) struct TestSource {
+    tmp: TempDir,
+    pub(crate) source_path: PathBuf,
+    pub(crate) archive_path: PathBuf,
+    pub(crate) target_path: PathBuf,
+}
+
+impl TestSource {
+    /// Creates an empty source directory, but neither the archive nor the target directory.
+    pub(crate) fn new() -> Self {
+        let tmp = tempdir().unwrap();
+        let source_path = tmp.path().join("source");
+        let archive_path = tmp.path().join("archive.zip");
+        let target_path = tmp.path().join("target");
+        fs::create_dir_all(&source_path).unwrap();
+
+        TestSource {
+            tmp,
+            source_path,
+            archive_path,
+            target_path,
+        }
+    }
+
+    /// Writes a file below the source directory, and creates its parent directories.
+    pub(crate) fn write(&self, name: &str, contents: impl AsRef<[u8]>) {
+        let path = self.source_path.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    /// Returns a path next to the source directory, such as for additional archives.
+    pub(crate) fn temp_path(&self, name: &str) -> PathBuf {
+        self.tmp.path().join(name)
+    }
+}
+
+/// Returns the sorted entry names of the archive.
+pub(crate) fn entry_names(archive_path: &Path) -> Vec<String> {
+    let archive = ZipArchive::new(File::open(archive_path).unwrap()).unwrap();
+    let mut names: Vec<String> = archive.file_names().map(String::from).collect();
+    names.sort();
+    names
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use crate::default_entry_handler::DefaultEntryHandler;
+    use crate::deflate::walk_options::{SymlinkPolicy, WalkOptions};
+    use crate::deflate::zip_writer_extensions::ZipWriterExtensions;
+    use crate::tests::test_source::{TestSource, entry_names};
+    use std::fs::File;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    #[cfg(unix)]
+    #[test]
+    fn create_skips_symlinks_to_ancestors_by_default() {
+        // Arrange
+        let source = create_source();
+        std::os::unix::fs::symlink("..", source.source_path.join("a/loop")).unwrap();
+
+        // Act
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+        zip_writer
+            .create_from_directory_with_options(
+                &source.source_path,
+                |_| SimpleFileOptions::default(),
+                &DefaultEntryHandler,
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+
+        // Assert
+        assert_eq!(
+            entry_names(&source.archive_path),
+            vec![
+                "a/",
+                "a/b/",
+                "a/b/deep.txt",
+                "a/loop/",
+                "a/one.txt",
+                "root.txt"
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn create_follows_or_skips_symlinked_directories_by_policy() {
+        // Arrange
+        let source = create_source();
+        std::os::unix::fs::symlink("a/b", source.source_path.join("link")).unwrap();
+
+        // Act
+        let followed = create_archive(
+            &source,
+            &WalkOptions::default().symlinks(SymlinkPolicy::Follow),
+        );
+        let not_followed = create_archive(
+            &source,
+            &WalkOptions::default().symlinks(SymlinkPolicy::DontFollow),
+        );
+
+        // Assert
+        assert!(followed.contains(&"link/deep.txt".to_string()));
+        assert!(not_followed.contains(&"link/".to_string()));
+        assert!(!not_followed.contains(&"link/deep.txt".to_string()));
+    }
+
+    #[test]
+    fn create_with_max_depth_does_not_descend_further() {
+        // Arrange
+        let source = create_source();
+
+        // Act
+        let depth_one = create_archive(&source, &WalkOptions::default().max_depth(1));
+        let depth_two = create_archive(&source, &WalkOptions::default().max_depth(2));
+
+        // Assert
+        assert_eq!(depth_one, vec!["a/", "root.txt"]);
+        assert_eq!(depth_two, vec!["a/", "a/b/", "a/one.txt", "root.txt"]);
+    }
+
+    #[test]
+    fn create_on_same_file_system_visits_subdirectories() {
+        // Arrange
+        let source = create_source();
+
+        // Act
+        let names = create_archive(&source, &WalkOptions::default().same_file_system(true));
+
+        // Assert
+        assert_eq!(
+            names,
+            vec!["a/", "a/b/", "a/b/deep.txt", "a/one.txt", "root.txt"]
+        );
+    }
+
+    fn create_source() -> TestSource {
+        let source = TestSource::new();
+        source.write("root.txt", "root");
+        source.write("a/one.txt", "one");
+        source.write("a/b/deep.txt", "deep");
+        source
+    }
+
+    fn create_archive(source: &TestSource, walk_options: &WalkOptions) -> Vec<String> {
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+        zip_writer
+            .create_from_directory_with_walk_options(
+                &source.source_path,
+                |_| SimpleFileOptions::default(),
+                &DefaultEntryHandler,
+                walk_options,
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+        entry_names(&source.archive_path)
+    }
+}
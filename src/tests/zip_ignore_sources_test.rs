@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use crate::deflate::zip_ignore_entry_handler::ZipIgnoreEntryHandler;
+    use crate::deflate::zip_writer_extensions::ZipWriterExtensions;
+    use crate::tests::test_source::{TestSource, entry_names};
+    use std::fs::File;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn zip_ignore_honors_configured_ignore_filenames() {
+        // Arrange
+        let source = TestSource::new();
+        source.write("a.txt", "keep");
+        source.write("b.txt", "ignored by .ignore");
+        source.write("c.txt", "ignored by .zipignore");
+        source.write(".ignore", "b.txt\n");
+        source.write(".zipignore", "c.txt\n");
+        let handler = ZipIgnoreEntryHandler::new().ignore_filenames(&[".ignore"]);
+
+        // Act
+        let names = create_archive(&source, &handler);
+
+        // Assert
+        assert!(names.contains(&"a.txt".to_string()));
+        assert!(names.contains(&"c.txt".to_string()));
+        assert!(!names.contains(&"b.txt".to_string()));
+    }
+
+    #[test]
+    fn zip_ignore_later_ignore_filenames_take_precedence() {
+        // Arrange
+        let source = TestSource::new();
+        source.write("a.log", "re-included");
+        source.write("b.log", "ignored");
+        source.write(".ignore", "*.log\n");
+        source.write(".zipignore", "!a.log\n");
+        let handler = ZipIgnoreEntryHandler::new().ignore_filenames(&[".ignore", ".zipignore"]);
+
+        // Act
+        let names = create_archive(&source, &handler);
+
+        // Assert
+        assert!(names.contains(&"a.log".to_string()));
+        assert!(!names.contains(&"b.log".to_string()));
+    }
+
+    #[test]
+    fn zip_ignore_honors_git_rules_only_if_enabled() {
+        // Arrange
+        let source = TestSource::new();
+        source.write("main.rs", "fn main() {}");
+        source.write("build.log", "ignored by .gitignore");
+        source.write("notes.txt", "ignored by .git/info/exclude");
+        source.write("sub/data.tmp", "ignored by sub/.gitignore");
+        source.write("sub/keep.txt", "keep");
+        source.write(".gitignore", "*.log\n");
+        source.write("sub/.gitignore", "*.tmp\n");
+        source.write(".git/info/exclude", "notes.txt\n");
+
+        // Act
+        let default_names = create_archive(&source, &ZipIgnoreEntryHandler::new());
+        let git_names = create_archive(
+            &source,
+            &ZipIgnoreEntryHandler::new()
+                .git_ignore(true)
+                .git_exclude(true)
+                .patterns(&[".git/"])
+                .unwrap(),
+        );
+
+        // Assert
+        assert!(default_names.contains(&"build.log".to_string()));
+        assert!(default_names.contains(&"notes.txt".to_string()));
+        assert!(default_names.contains(&"sub/data.tmp".to_string()));
+
+        assert!(git_names.contains(&"main.rs".to_string()));
+        assert!(git_names.contains(&"sub/keep.txt".to_string()));
+        assert!(!git_names.contains(&"build.log".to_string()));
+        assert!(!git_names.contains(&"notes.txt".to_string()));
+        assert!(!git_names.contains(&"sub/data.tmp".to_string()));
+        assert!(!git_names.iter().any(|name| name.starts_with(".git/")));
+    }
+
+    #[test]
+    fn zip_ignore_patterns_take_precedence_over_ignore_files() {
+        // Arrange
+        let source = TestSource::new();
+        source.write("a.txt", "re-included by pattern");
+        source.write("target/debug/app", "ignored by pattern");
+        source.write(".zipignore", "a.txt\n");
+        let handler = ZipIgnoreEntryHandler::new()
+            .patterns(&["target/", "!a.txt"])
+            .unwrap();
+
+        // Act
+        let names = create_archive(&source, &handler);
+
+        // Assert
+        assert!(names.contains(&"a.txt".to_string()));
+        assert!(!names.iter().any(|name| name.starts_with("target/")));
+    }
+
+    #[test]
+    fn zip_ignore_rejects_invalid_patterns() {
+        // Act
+        let result = ZipIgnoreEntryHandler::new().patterns(&["assets/{a,b"]);
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    fn create_archive(source: &TestSource, handler: &ZipIgnoreEntryHandler) -> Vec<String> {
+        let mut zip_writer = ZipWriter::new(File::create(&source.archive_path).unwrap());
+        zip_writer
+            .create_from_directory_with_options(
+                &source.source_path,
+                |_| SimpleFileOptions::default(),
+                handler,
+            )
+            .unwrap();
+        zip_writer.finish().unwrap();
+        entry_names(&source.archive_path)
+    }
+}
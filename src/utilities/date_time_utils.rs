@@ -0,0 +1,91 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zip::DateTime;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Converts a MS-DOS timestamp stored in a ZIP entry to a `SystemTime`. DOS timestamps carry no
+/// time zone; they are deliberately interpreted as UTC, not local time, so that the result does
+/// not depend on the host (see `ExtractOptions::restore_timestamps`). Returns `None` for
+/// out-of-range values.
+pub(crate) fn date_time_to_system_time(date_time: &DateTime) -> Option<SystemTime> {
+    let (month, day) = (date_time.month(), date_time.day());
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    if date_time.hour() > 23 || date_time.minute() > 59 || date_time.second() > 59 {
+        return None;
+    }
+    let days = days_from_civil(date_time.year() as i64, month as i64, day as i64);
+    let seconds = days * SECONDS_PER_DAY
+        + date_time.hour() as i64 * 3600
+        + date_time.minute() as i64 * 60
+        + date_time.second() as i64;
+    Some(UNIX_EPOCH + Duration::from_secs(u64::try_from(seconds).ok()?))
+}
+
+/// Converts seconds since the Unix epoch, as stored in the extended timestamp extra field, to a
+/// `SystemTime`. The field holds a signed 32-bit value, so times before 1970 are negative.
+pub(crate) fn unix_seconds_to_system_time(seconds: u32) -> SystemTime {
+    let seconds = seconds as i32;
+    if seconds < 0 {
+        UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs() as u64)
+    } else {
+        UNIX_EPOCH + Duration::from_secs(seconds as u64)
+    }
+}
+
+/// Converts seconds since the Unix epoch to a MS-DOS timestamp that holds the UTC time, not the
+/// local time, so that the result does not depend on the host. Times before 1980-01-01 or after
+/// 2107-12-31 are clamped to the range a DOS timestamp can represent; odd seconds are rounded down
+/// by the DOS format.
+pub(crate) fn unix_seconds_to_date_time(seconds: i64) -> DateTime {
+    let days = seconds.div_euclid(SECONDS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    if year < 1980 {
+        return DateTime::default();
+    }
+    if year > 2107 {
+        return DateTime::from_date_and_time(2107, 12, 31, 23, 59, 58).unwrap_or_default();
+    }
+    let seconds_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+    DateTime::from_date_and_time(
+        year as u16,
+        month as u8,
+        day as u8,
+        (seconds_of_day / 3600) as u8,
+        (seconds_of_day % 3600 / 60) as u8,
+        (seconds_of_day % 60) as u8,
+    )
+    .unwrap_or_default()
+}
+
+/// Returns the number of days since 1970-01-01 for the given proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the proleptic Gregorian date `(year, month, day)` of the given number of days since
+/// 1970-01-01; the inverse of `days_from_civil`.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -1,7 +1,8 @@
-use std::fs::File;
+use std::fs::{self, File, OpenOptions};
+use std::hash::{BuildHasher, RandomState};
 use std::io;
-use std::io::{Error, ErrorKind, Write};
-use std::path::{Component, PathBuf};
+use std::io::{Error, ErrorKind, Read, Write};
+use std::path::{Component, Path, PathBuf};
 
 /// Writes all bytes to a file.
 pub fn file_write_all_bytes(path: PathBuf, bytes: &[u8], overwrite: bool) -> io::Result<usize> {
@@ -16,6 +17,65 @@ pub fn file_write_all_bytes(path: PathBuf, bytes: &[u8], overwrite: bool) -> io:
     file.write(bytes)
 }
 
+/// The prefix of the temporary files that data is written to before it replaces a file.
+pub(crate) const TEMP_FILE_PREFIX: &str = ".zipext-";
+
+/// Copies all bytes from a reader to a file. The data is streamed through a fixed-size buffer, so
+/// memory usage does not depend on the amount of data written.
+///
+/// The data is written to a temporary file in the same directory, which replaces the file only
+/// once the reader is exhausted; if reading fails, an existing file is left intact.
+pub fn file_write_from_reader<R: Read + ?Sized>(
+    path: PathBuf,
+    reader: &mut R,
+    overwrite: bool,
+) -> io::Result<u64> {
+    if path.exists() && !overwrite {
+        return Err(Error::new(
+            ErrorKind::AlreadyExists,
+            "The specified file already exists.",
+        ));
+    }
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let (mut file, temp_path) = create_temp_file(directory, TEMP_FILE_PREFIX)?;
+    let written = io::copy(reader, &mut file).and_then(|bytes_written| {
+        file.flush()?;
+        Ok(bytes_written)
+    });
+    drop(file);
+    let result = written.and_then(|bytes_written| {
+        fs::rename(&temp_path, &path)?;
+        Ok(bytes_written)
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Creates a new, empty file in `directory`, whose name starts with `prefix` and ends with a
+/// random suffix. The name cannot be predicted, so other users cannot create the file in advance.
+pub(crate) fn create_temp_file(directory: &Path, prefix: &str) -> io::Result<(File, PathBuf)> {
+    loop {
+        // Every `RandomState` is seeded with different random keys.
+        let suffix = RandomState::new().hash_one(std::process::id());
+        let path = directory.join(format!("{}{:016x}.tmp", prefix, suffix));
+        match OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => return Ok((file, path)),
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(error),
+        }
+    }
+}
+
 /// Returns a relative path from one path to another.
 pub(crate) fn make_relative_path(root: &PathBuf, current: &PathBuf) -> PathBuf {
     let mut result = PathBuf::new();
@@ -1 +1,2 @@
+pub mod date_time_utils;
 pub mod file_utils;